@@ -0,0 +1,261 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use maud::Markup;
+use maud::html;
+
+use crate::db::TargetStatus;
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::types::Date;
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 320.0;
+const MARGIN_LEFT: f64 = 64.0;
+const MARGIN_RIGHT: f64 = 16.0;
+const MARGIN_TOP: f64 = 16.0;
+const MARGIN_BOTTOM: f64 = 32.0;
+
+/// The most date labels to fit along the x axis.
+const MAX_X_LABELS: usize = 8;
+
+/// Roughly how many gridlines to draw along the y axis.
+const Y_TICKS: f64 = 4.0;
+
+/// How the daily values are drawn.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ChartKind {
+    Line,
+    Bar,
+}
+
+impl ChartKind {
+    pub const ALL: [ChartKind; 2] = [Self::Line, Self::Bar];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Line => "line",
+            Self::Bar => "bar",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Line => "Line",
+            Self::Bar => "Bar",
+        }
+    }
+}
+
+impl TryFrom<&str> for ChartKind {
+    type Error = AppError;
+
+    fn try_from(value: &str) -> Fallible<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|k| k.as_str() == value)
+            .ok_or_else(|| AppError::bad_request(format!("Invalid chart kind: {value}")))
+    }
+}
+
+/// The target range on a day. Either bound may be missing.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Band {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// A day on the chart. All values are in the unit the chart is labelled with.
+pub struct ChartDay {
+    pub date: Date,
+    /// The day's total, or `None` if nothing was logged.
+    pub value: Option<f64>,
+    pub average: Option<f64>,
+    pub band: Option<Band>,
+    pub status: Option<TargetStatus>,
+}
+
+/// The trailing average of each value over `window` values, ignoring missing
+/// ones. `None` where the whole window is missing.
+pub fn rolling_average(values: &[Option<f64>], window: usize) -> Vec<Option<f64>> {
+    (0..values.len())
+        .map(|i| {
+            let start = (i + 1).saturating_sub(window);
+            let present: Vec<f64> = values[start..=i].iter().flatten().copied().collect();
+            if present.is_empty() {
+                None
+            } else {
+                Some(present.iter().sum::<f64>() / present.len() as f64)
+            }
+        })
+        .collect()
+}
+
+/// Maps days and values to coordinates in the plot area.
+struct Scale {
+    days: usize,
+    y_max: f64,
+}
+
+impl Scale {
+    fn slot(&self) -> f64 {
+        (WIDTH - MARGIN_LEFT - MARGIN_RIGHT) / self.days as f64
+    }
+
+    /// The left edge of the day's slot.
+    fn x_start(&self, index: usize) -> f64 {
+        MARGIN_LEFT + index as f64 * self.slot()
+    }
+
+    /// The centre of the day's slot.
+    fn x(&self, index: usize) -> f64 {
+        self.x_start(index) + self.slot() / 2.0
+    }
+
+    fn y(&self, value: f64) -> f64 {
+        let bottom = HEIGHT - MARGIN_BOTTOM;
+        bottom - value.clamp(0.0, self.y_max) / self.y_max * (bottom - MARGIN_TOP)
+    }
+}
+
+/// A round step between gridlines, so that about `Y_TICKS` of them cover
+/// `max`.
+fn tick_step(max: f64) -> f64 {
+    let raw = max / Y_TICKS;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .find(|m| m * magnitude >= raw)
+        .unwrap_or(10.0);
+    step * magnitude
+}
+
+/// Render the days as an inline SVG chart, with target bands behind the
+/// values and the rolling average drawn over them.
+pub fn render_chart(kind: ChartKind, days: &[ChartDay], unit: &str) -> Markup {
+    let highest = days
+        .iter()
+        .flat_map(|day| {
+            let band = day.band.map(|b| [b.min, b.max]).unwrap_or([None, None]);
+            [day.value, day.average, band[0], band[1]]
+        })
+        .flatten()
+        .fold(0.0, f64::max);
+    let step = tick_step(if highest > 0.0 { highest } else { 1.0 });
+    let scale = Scale {
+        days: days.len().max(1),
+        y_max: (highest / step).ceil().max(1.0) * step,
+    };
+    let ticks: Vec<f64> = (0..)
+        .map(|i| i as f64 * step)
+        .take_while(|tick| *tick <= scale.y_max + step / 2.0)
+        .collect();
+    let label_every = days.len().div_ceil(MAX_X_LABELS).max(1);
+    let format_value = |v: f64| {
+        if step >= 1.0 {
+            format!("{v:.0}")
+        } else {
+            format!("{v:.1}")
+        }
+    };
+    html! {
+        svg .chart xmlns="http://www.w3.org/2000/svg" viewBox=(format!("0 0 {WIDTH} {HEIGHT}")) role="img" {
+            @for tick in &ticks {
+                line .grid x1=(MARGIN_LEFT) x2=(WIDTH - MARGIN_RIGHT) y1=(scale.y(*tick)) y2=(scale.y(*tick)) {}
+                text .y-label x=(MARGIN_LEFT - 6.0) y=(scale.y(*tick) + 4.0) { (format_value(*tick)) }
+            }
+            text .y-label x=(MARGIN_LEFT - 6.0) y=(MARGIN_TOP - 4.0) { (unit) }
+            @for (start, end, band) in band_runs(days) {
+                rect .band
+                    x=(scale.x_start(start))
+                    y=(band.max.map(|max| scale.y(max)).unwrap_or(MARGIN_TOP))
+                    width=(scale.x_start(end + 1) - scale.x_start(start))
+                    height=(band.min.map(|min| scale.y(min)).unwrap_or(HEIGHT - MARGIN_BOTTOM) - band.max.map(|max| scale.y(max)).unwrap_or(MARGIN_TOP)) {}
+            }
+            @match kind {
+                ChartKind::Bar => {
+                    @for (i, day) in days.iter().enumerate() {
+                        @if let Some(value) = day.value {
+                            rect .bar .(day.status.map(|s| s.as_str()).unwrap_or("untargeted"))
+                                x=(scale.x(i) - scale.slot() * 0.35)
+                                y=(scale.y(value))
+                                width=(scale.slot() * 0.7)
+                                height=(scale.y(0.0) - scale.y(value)) {
+                                title { (day.date.humanize_short()) ": " (format_value(value)) " " (unit) }
+                            }
+                        }
+                    }
+                }
+                ChartKind::Line => {
+                    path .value d=(line_path(&scale, days.iter().map(|day| day.value))) {}
+                    @for (i, day) in days.iter().enumerate() {
+                        @if let Some(value) = day.value {
+                            circle .point .(day.status.map(|s| s.as_str()).unwrap_or("untargeted"))
+                                cx=(scale.x(i)) cy=(scale.y(value)) r="3" {
+                                title { (day.date.humanize_short()) ": " (format_value(value)) " " (unit) }
+                            }
+                        }
+                    }
+                }
+            }
+            path .average d=(line_path(&scale, days.iter().map(|day| day.average))) {}
+            line .axis x1=(MARGIN_LEFT) x2=(WIDTH - MARGIN_RIGHT) y1=(scale.y(0.0)) y2=(scale.y(0.0)) {}
+            @for (i, day) in days.iter().enumerate().step_by(label_every) {
+                text .x-label x=(scale.x(i)) y=(HEIGHT - MARGIN_BOTTOM + 18.0) { (day.date.humanize_short()) }
+            }
+        }
+        ul .chart-legend {
+            li { span .swatch .value {} "Daily total" }
+            li { span .swatch .average {} "7-day average" }
+            li { span .swatch .band {} "Target" }
+        }
+    }
+}
+
+/// Runs of consecutive days sharing the same target band, as inclusive
+/// index ranges.
+fn band_runs(days: &[ChartDay]) -> Vec<(usize, usize, Band)> {
+    let mut runs: Vec<(usize, usize, Band)> = Vec::new();
+    for (i, day) in days.iter().enumerate() {
+        let Some(band) = day.band else {
+            continue;
+        };
+        match runs.last_mut() {
+            Some((_, end, last)) if *end + 1 == i && *last == band => *end = i,
+            _ => runs.push((i, i, band)),
+        }
+    }
+    runs
+}
+
+/// An SVG path through the values, broken wherever one is missing.
+fn line_path(scale: &Scale, values: impl Iterator<Item = Option<f64>>) -> String {
+    let mut d = String::new();
+    let mut pen_down = false;
+    for (i, value) in values.enumerate() {
+        match value {
+            Some(value) => {
+                let command = if pen_down { 'L' } else { 'M' };
+                d.push_str(&format!(
+                    "{command}{:.1},{:.1} ",
+                    scale.x(i),
+                    scale.y(value)
+                ));
+                pen_down = true;
+            }
+            None => pen_down = false,
+        }
+    }
+    d
+}
@@ -15,7 +15,12 @@
 use std::process::ExitCode;
 
 use clap::Parser;
+use clap::Subcommand;
 
+use crate::config::Config;
+use crate::db::Db;
+use crate::error::Fallible;
+use crate::migrations::latest_version;
 use crate::www::start_server;
 
 #[derive(Parser)]
@@ -23,12 +28,30 @@ use crate::www::start_server;
 enum Command {
     /// Start a server.
     Serve,
+    /// Manage the database.
+    Db {
+        #[command(subcommand)]
+        command: DbCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum DbCommand {
+    /// Apply pending schema migrations.
+    Migrate {
+        /// List the pending migrations without applying them.
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 pub async fn entrypoint() -> ExitCode {
     let c: Command = Command::parse();
     let res = match c {
         Command::Serve => start_server().await,
+        Command::Db { command } => match command {
+            DbCommand::Migrate { dry_run } => migrate(dry_run),
+        },
     };
     match res {
         Ok(_) => ExitCode::SUCCESS,
@@ -38,3 +61,38 @@ pub async fn entrypoint() -> ExitCode {
         }
     }
 }
+
+fn migrate(dry_run: bool) -> Fallible<()> {
+    let config: Config = Config::load()?;
+    println!("Database: {}", config.db_path.display());
+    let mut db: Db = Db::open(&config.db_path)?;
+    if dry_run {
+        let pending = db.pending_migrations()?;
+        println!(
+            "Schema version: {} (latest: {})",
+            db.schema_version()?,
+            latest_version()
+        );
+        if pending.is_empty() {
+            println!("No pending migrations.");
+        }
+        for migration in pending {
+            println!(
+                "Pending migration {:04}: {}",
+                migration.version, migration.name
+            );
+        }
+    } else {
+        let applied = db.migrate()?;
+        if applied.is_empty() {
+            println!("No pending migrations.");
+        }
+        for migration in applied {
+            println!(
+                "Applied migration {:04}: {}",
+                migration.version, migration.name
+            );
+        }
+    }
+    Ok(())
+}
@@ -19,18 +19,23 @@ use serde::Deserialize;
 
 use crate::error::AppError;
 use crate::error::Fallible;
+use crate::types::EnergyUnit;
 
 pub struct Config {
     /// Absolute, canonicalized path to the SQLite3 database.
     pub db_path: PathBuf,
     /// Port in which to run the server.
     pub port: u16,
+    /// The unit energy is displayed in.
+    pub energy_unit: EnergyUnit,
 }
 
 #[derive(Deserialize)]
 struct ConfigFile {
     database_path: PathBuf,
     port: u16,
+    #[serde(default)]
+    energy_unit: EnergyUnit,
 }
 
 impl Config {
@@ -38,7 +43,7 @@ impl Config {
     pub fn load() -> Fallible<Self> {
         // Get the home directory
         let home = std::env::var("HOME")
-            .map_err(|_| AppError::new("HOME environment variable not set"))?;
+            .map_err(|_| AppError::internal("HOME environment variable not set"))?;
 
         // Construct the config file path
         let config_path = PathBuf::from(home)
@@ -48,7 +53,7 @@ impl Config {
 
         // Read the config file
         let contents = fs::read_to_string(&config_path).map_err(|e| {
-            AppError::new(format!(
+            AppError::internal(format!(
                 "Failed to read config file at {}: {}",
                 config_path.display(),
                 e
@@ -57,11 +62,11 @@ impl Config {
 
         // Parse the TOML
         let config_file: ConfigFile = toml::from_str(&contents)
-            .map_err(|e| AppError::new(format!("Failed to parse config file: {}", e)))?;
+            .map_err(|e| AppError::internal(format!("Failed to parse config file: {}", e)))?;
 
         // Canonicalize the database path
         let db_path = fs::canonicalize(&config_file.database_path).map_err(|e| {
-            AppError::new(format!(
+            AppError::internal(format!(
                 "Failed to canonicalize database path {}: {}",
                 config_file.database_path.display(),
                 e
@@ -71,6 +76,7 @@ impl Config {
         Ok(Config {
             db_path,
             port: config_file.port,
+            energy_unit: config_file.energy_unit,
         })
     }
 }
@@ -12,23 +12,77 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
 
 use chrono::DateTime;
 use chrono::Utc;
 use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use rusqlite::Params;
+use rusqlite::Row;
 use rusqlite::config::DbConfig;
 use rusqlite::params;
+use tokio::task::spawn_blocking;
 
+use crate::error::AppError;
 use crate::error::Fallible;
+use crate::migrations;
+use crate::migrations::Migration;
 use crate::types::BasicUnit;
 use crate::types::Date;
+use crate::types::EnergyUnit;
+use crate::types::Nutrient;
 use crate::types::Nutrition;
+use crate::types::Time;
 
 pub struct Db {
     conn: Connection,
 }
 
+/// How long to wait for another connection, like the CLI's, to release a lock
+/// on the database before giving up.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A database shared by the request handlers.
+///
+/// SQLite calls block, so they are run on tokio's blocking thread pool rather
+/// than on the async workers. Requests take turns on the connection: while
+/// one is running, the others wait for it rather than failing.
+#[derive(Clone)]
+pub struct SharedDb {
+    db: Arc<Mutex<Db>>,
+}
+
+impl SharedDb {
+    pub fn new(db: Db) -> Self {
+        Self {
+            db: Arc::new(Mutex::new(db)),
+        }
+    }
+
+    /// Run a closure against the database, once it's free.
+    pub async fn run<T, F>(&self, f: F) -> Fallible<T>
+    where
+        F: FnOnce(&Db) -> Fallible<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let db = self.db.clone();
+        spawn_blocking(move || {
+            // A panic in another closure poisons the mutex, but transactions
+            // roll back on drop, so the connection is still usable.
+            let db = db.lock().unwrap_or_else(PoisonError::into_inner);
+            f(&db)
+        })
+        .await
+        .map_err(|e| AppError::internal(format!("database task failed: {e}")))?
+    }
+}
+
 pub type FoodId = i64;
 
 /// The name of a food.
@@ -74,6 +128,15 @@ pub struct CreateFoodInput {
     pub carbs_sugars: Sugars,
     pub fibre: Fibre,
     pub sodium: Sodium,
+    /// If set, a serving is created along with the food.
+    pub serving: Option<NewServing>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A serving to create along with a food.
+pub struct NewServing {
+    pub serving_name: ServingName,
+    pub serving_amount: f64,
     pub created_at: DateTime<Utc>,
 }
 
@@ -98,21 +161,17 @@ pub struct FoodEntry {
     pub carbs_sugars: Sugars,
     pub fibre: Fibre,
     pub sodium: Sodium,
+    /// Whether the food is hidden from the food pickers.
+    pub archived: bool,
 }
 
-impl FoodEntry {
-    pub fn nutrition(&self) -> Nutrition {
-        Nutrition {
-            energy: self.energy,
-            protein: self.protein,
-            fat: self.fat,
-            fat_saturated: self.fat_saturated,
-            carbs: self.carbs,
-            carbs_sugars: self.carbs_sugars,
-            fibre: self.fibre,
-            sodium: self.sodium,
-        }
-    }
+/// How many records use a food.
+pub struct FoodUsage {
+    pub entries: i64,
+    /// The number of recipes that use the food as an ingredient.
+    pub recipes: i64,
+    /// The number of meal templates that include the food.
+    pub templates: i64,
 }
 
 /// Data needed to edit an existing food.
@@ -129,6 +188,11 @@ pub struct EditFoodInput {
     pub carbs_sugars: Sugars,
     pub fibre: Fibre,
     pub sodium: Sodium,
+    /// If set, entries for this food on or after this date are updated to
+    /// the new nutrition values.
+    pub correct_from: Option<Date>,
+    /// If set, a serving is added to the food.
+    pub serving: Option<NewServing>,
 }
 
 pub type ServingId = i64;
@@ -147,6 +211,231 @@ pub struct Serving {
     pub serving_amount: f64,
 }
 
+/// How many records use a serving.
+pub struct ServingUsage {
+    pub entries: i64,
+    pub ingredients: i64,
+    pub template_lines: i64,
+}
+
+/// Data needed to create a new recipe.
+pub struct CreateRecipeInput {
+    pub name: FoodName,
+    pub brand: BrandName,
+    pub serving_unit: BasicUnit,
+    pub cooked_weight: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Data needed to edit an existing recipe.
+pub struct EditRecipeInput {
+    pub food_id: FoodId,
+    pub name: FoodName,
+    pub brand: BrandName,
+    pub serving_unit: BasicUnit,
+    pub cooked_weight: f64,
+    /// If set, entries for this recipe on or after this date are updated to
+    /// the new nutrition values.
+    pub correct_from: Option<Date>,
+}
+
+pub struct Recipe {
+    pub food_id: FoodId,
+    pub cooked_weight: f64,
+}
+
+pub type IngredientId = i64;
+
+pub struct CreateIngredientInput {
+    pub recipe_id: FoodId,
+    pub food_id: FoodId,
+    pub serving_id: Option<ServingId>,
+    pub amount: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An ingredient line in a recipe, joined with its food and serving.
+pub struct Ingredient {
+    pub ingredient_id: IngredientId,
+    pub food_id: FoodId,
+    pub name: FoodName,
+    pub brand: BrandName,
+    pub serving_unit: BasicUnit,
+    pub serving_name: Option<ServingName>,
+    pub amount: f64,
+    /// The nutrition contributed by this ingredient to the whole recipe.
+    pub nutrition: Nutrition,
+}
+
+pub type SlotId = i64;
+
+pub struct CreateMealSlotInput {
+    pub name: String,
+    pub starts_at: Option<Time>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A meal that entries are grouped into.
+pub struct MealSlot {
+    pub slot_id: SlotId,
+    pub name: String,
+    /// When the meal starts. `None` for slots that are never picked by
+    /// default.
+    pub starts_at: Option<Time>,
+}
+
+impl MealSlot {
+    /// Pick the slot for food eaten at the given time: the slot that started
+    /// most recently. Before the first slot of the day, that's the last slot
+    /// of the previous day.
+    pub fn default_for(slots: &[MealSlot], time: Time) -> Option<SlotId> {
+        let timed = slots
+            .iter()
+            .filter_map(|slot| slot.starts_at.map(|t| (t, slot.slot_id)));
+        let latest_before = timed.clone().filter(|(t, _)| *t <= time).max();
+        latest_before
+            .or_else(|| timed.max())
+            .map(|(_, slot_id)| slot_id)
+    }
+}
+
+pub type TemplateId = i64;
+
+pub struct CreateMealTemplateInput {
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A named list of foods that are logged together.
+pub struct MealTemplate {
+    pub template_id: TemplateId,
+    pub name: String,
+}
+
+pub type TemplateLineId = i64;
+
+pub struct CreateTemplateLineInput {
+    pub template_id: TemplateId,
+    pub food_id: FoodId,
+    pub serving_id: Option<ServingId>,
+    pub amount: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A line in a meal template, joined with its food and serving.
+pub struct TemplateLine {
+    pub line_id: TemplateLineId,
+    pub food_id: FoodId,
+    pub name: FoodName,
+    pub brand: BrandName,
+    pub serving_unit: BasicUnit,
+    pub serving_id: Option<ServingId>,
+    pub serving_name: Option<ServingName>,
+    pub amount: f64,
+}
+
+/// Data needed to log a meal template.
+pub struct LogMealTemplateInput {
+    pub template_id: TemplateId,
+    pub date: Date,
+    /// The amount of each line is multiplied by this.
+    pub factor: f64,
+    pub eaten_at: Time,
+    pub slot_id: Option<SlotId>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub type ProfileId = i64;
+
+/// The profile that applies to days without a rule of their own. It can't be
+/// deleted.
+pub const DEFAULT_PROFILE_ID: ProfileId = 1;
+
+pub struct CreateTargetProfileInput {
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A named set of targets.
+pub struct TargetProfile {
+    pub profile_id: ProfileId,
+    pub name: String,
+}
+
+/// The profile that applies on some day.
+pub struct DayProfile {
+    pub profile: TargetProfile,
+    /// Whether the profile was chosen for this specific day, rather than by
+    /// the weekday rules.
+    pub overridden: bool,
+}
+
+/// A daily target for a nutrient.
+#[derive(Clone)]
+pub struct Target {
+    pub nutrient: Nutrient,
+    /// The date from which the target applies.
+    pub effective_from: Date,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+/// How a daily total compares to its target.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TargetStatus {
+    Under,
+    Within,
+    Over,
+}
+
+impl TargetStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Under => "under",
+            Self::Within => "within",
+            Self::Over => "over",
+        }
+    }
+}
+
+impl Target {
+    pub fn status(&self, total: f64) -> TargetStatus {
+        match (self.min, self.max) {
+            (Some(min), _) if total < min => TargetStatus::Under,
+            (_, Some(max)) if total > max => TargetStatus::Over,
+            _ => TargetStatus::Within,
+        }
+    }
+
+    /// Human-readable description of the target, e.g. "≥ 120 g".
+    pub fn describe(&self, energy_unit: EnergyUnit) -> String {
+        // Round to one decimal place, dropping it if it's zero.
+        let fmt = |v: f64| {
+            let v = (self.nutrient.display_value(v, energy_unit) * 10.0).round() / 10.0;
+            v.to_string()
+        };
+        let unit = self.nutrient.display_unit(energy_unit);
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => format!("{}–{} {unit}", fmt(min), fmt(max)),
+            (Some(min), None) => format!("≥ {} {unit}", fmt(min)),
+            (None, Some(max)) => format!("≤ {} {unit}", fmt(max)),
+            (None, None) => "—".to_string(),
+        }
+    }
+}
+
+/// Data needed to set the target for a nutrient from some date.
+pub struct SetTargetInput {
+    pub profile_id: ProfileId,
+    pub nutrient: Nutrient,
+    pub effective_from: Date,
+    /// If both `min` and `max` are `None`, the nutrient has no target from
+    /// `effective_from` on.
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub created_at: DateTime<Utc>,
+}
+
 pub type EntryId = i64;
 
 pub struct CreateEntryInput {
@@ -154,56 +443,182 @@ pub struct CreateEntryInput {
     pub food_id: FoodId,
     pub serving_id: Option<ServingId>,
     pub amount: f64,
+    pub eaten_at: Time,
+    pub slot_id: Option<SlotId>,
     pub created_at: DateTime<Utc>,
 }
 
-pub struct Entry {
+/// Which of a day's entries to copy.
+pub enum EntrySelection {
+    All,
+    /// The entries in a meal, or in no meal if `None`.
+    Slot(Option<SlotId>),
+    /// The given entries. Entries from other days are ignored.
+    Entries(Vec<EntryId>),
+}
+
+/// Data needed to copy entries from one day to another.
+pub struct CopyEntriesInput {
+    pub from: Date,
+    pub to: Date,
+    pub selection: EntrySelection,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Data needed to edit an existing entry.
+pub struct EditEntryInput {
     pub entry_id: EntryId,
+    pub date: Date,
     pub food_id: FoodId,
     pub serving_id: Option<ServingId>,
     pub amount: f64,
+    pub eaten_at: Time,
+    pub slot_id: Option<SlotId>,
+}
+
+pub struct Entry {
+    pub entry_id: EntryId,
+    pub date: Date,
+    /// The food that was logged, or `None` for quick-add entries.
+    pub food_id: Option<FoodId>,
+    /// What a quick-add entry was. `None` for entries of a food.
+    pub label: Option<String>,
+    pub serving_id: Option<ServingId>,
+    pub amount: f64,
+    /// The time of day the food was eaten.
+    pub eaten_at: Time,
+    /// The meal the entry belongs to.
+    pub slot_id: Option<SlotId>,
+    /// The nutrition of the food, per 100 of its base unit, at the time the
+    /// entry was logged. For quick-add entries, whose amount is always
+    /// [`QUICK_ADD_AMOUNT`], the nutrition of the whole entry.
+    pub food_nutrition: Nutrition,
+}
+
+/// The amount of every quick-add entry. Since nutrition is stored per 100,
+/// this makes the nutrition of the entry the stored values. The database
+/// rejects quick-add entries with any other amount, see migration 13.
+pub const QUICK_ADD_AMOUNT: f64 = 100.0;
+
+/// Data needed to log a quick-add entry.
+pub struct CreateQuickEntryInput {
+    pub date: Date,
+    pub label: String,
+    /// The nutrition of the whole entry.
+    pub nutrition: Nutrition,
+    pub eaten_at: Time,
+    pub slot_id: Option<SlotId>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Data needed to turn a quick-add entry into an entry of a new food. The
+/// entry becomes one serving of the food.
+pub struct ConvertQuickEntryInput {
+    pub entry_id: EntryId,
+    pub name: FoodName,
+    pub brand: BrandName,
+    pub serving_unit: BasicUnit,
+    pub serving_name: ServingName,
+    /// The size of the entry, in the base unit.
+    pub serving_amount: f64,
     pub created_at: DateTime<Utc>,
 }
 
-impl Entry {
-    pub fn nutrition(&self, db: &Db) -> Fallible<Nutrition> {
-        let food: FoodEntry = db.get_food(self.food_id)?;
+/// An entry, along with the food and serving data needed to display it.
+pub struct LogEntry {
+    pub entry: Entry,
+    /// The name of the food, or the label of a quick-add entry.
+    pub food_name: FoodName,
+    /// The brand of the food. Empty for quick-add entries.
+    pub food_brand: BrandName,
+    /// The base unit of the food. `None` for quick-add entries.
+    pub serving_unit: Option<BasicUnit>,
+    /// The name of the serving the amount is in, or `None` for the base unit.
+    pub serving_name: Option<ServingName>,
+    /// The nutrition of the amount eaten.
+    pub nutrition: Nutrition,
+}
 
-        // Get the amount of the food in its base unit. If there's a custom unit,
-        // multiply the amount by the unit's definition. Otherwise, use the base
-        // unit amount.
-        let amount_base: f64 = if let Some(serving_id) = self.serving_id {
-            let serving = db.get_serving_by_id(serving_id)?;
-            self.amount * serving.serving_amount
-        } else {
-            self.amount
-        };
-        let factor = amount_base / 100.0;
+impl LogEntry {
+    pub fn is_quick_add(&self) -> bool {
+        self.entry.food_id.is_none()
+    }
 
-        let nutrition = food.nutrition();
-        let nutrition = nutrition.scale(factor);
-        Ok(nutrition)
+    /// Describe the amount eaten, e.g. "2 slice" or "250 g". Quick-add
+    /// entries have no meaningful amount, so they get a dash.
+    pub fn describe_amount(&self) -> String {
+        match (&self.serving_name, self.serving_unit) {
+            (Some(serving_name), _) => format!("{} {}", self.entry.amount, serving_name),
+            (None, Some(unit)) => format!("{} {}", self.entry.amount, unit.as_str()),
+            (None, None) => "—".to_string(),
+        }
     }
 }
 
+/// The total nutrition of a day that has entries.
+pub struct DailyTotals {
+    pub date: Date,
+    pub nutrition: Nutrition,
+}
+
+/// A food that has been logged before, along with how it was last logged.
+pub struct LoggedFood {
+    pub food_id: FoodId,
+    pub name: FoodName,
+    pub brand: BrandName,
+    pub serving_unit: BasicUnit,
+    /// The serving of the last entry, or `None` if it was in the base unit.
+    pub serving_id: Option<ServingId>,
+    pub serving_name: Option<ServingName>,
+    /// The amount of the last entry.
+    pub amount: f64,
+}
+
 impl Db {
-    pub fn new(path: &Path) -> Fallible<Self> {
-        if path.exists() {
-            let conn = Connection::open(path)?;
-            conn.set_db_config(DbConfig::SQLITE_DBCONFIG_ENABLE_FKEY, true)?;
-            Ok(Self { conn })
-        } else {
-            let mut conn = Connection::open(path)?;
-            conn.set_db_config(DbConfig::SQLITE_DBCONFIG_ENABLE_FKEY, true)?;
-            let tx = conn.transaction()?;
-            tx.execute_batch(include_str!("schema.sql"))?;
-            tx.commit()?;
-            Ok(Self { conn })
-        }
+    /// Open the database. Does not apply migrations.
+    pub fn open(path: &Path) -> Fallible<Self> {
+        let conn = Connection::open(path)?;
+        conn.set_db_config(DbConfig::SQLITE_DBCONFIG_ENABLE_FKEY, true)?;
+        // With write-ahead logging, readers don't block the writer, and the
+        // other way around.
+        let _: String =
+            conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(Self { conn })
+    }
+
+    /// Return the schema version of the database.
+    pub fn schema_version(&self) -> Fallible<i64> {
+        migrations::current_version(&self.conn)
+    }
+
+    /// Return the schema migrations that have yet to be applied.
+    pub fn pending_migrations(&self) -> Fallible<Vec<&'static Migration>> {
+        migrations::pending(&self.conn)
+    }
+
+    /// Apply pending schema migrations, and return the ones that were applied.
+    pub fn migrate(&mut self) -> Fallible<Vec<&'static Migration>> {
+        migrations::migrate(&mut self.conn)
     }
 
     /// Create a new food.
     pub fn create_food(&self, input: CreateFoodInput) -> Fallible<FoodId> {
+        let tx = self.conn.unchecked_transaction()?;
+        let food_id = self.insert_food(&input)?;
+        if let Some(serving) = input.serving {
+            self.create_serving(ServingInput {
+                food_id,
+                serving_name: serving.serving_name,
+                serving_amount: serving.serving_amount,
+                created_at: serving.created_at,
+            })?;
+        }
+        tx.commit()?;
+        Ok(food_id)
+    }
+
+    fn insert_food(&self, input: &CreateFoodInput) -> Fallible<FoodId> {
         let sql = "
             insert into foods
                 (name, brand, serving_unit, energy, protein, fat, fat_saturated, carbs, carbs_sugars, fibre, sodium, created_at)
@@ -232,18 +647,75 @@ impl Db {
         Ok(food_id)
     }
 
-    /// Return summary information for all foods in the database.
+    /// Return summary information for all foods in the database that have
+    /// not been archived.
     pub fn list_foods(&self) -> Fallible<Vec<FoodListEntry>> {
+        self.list_foods_by_archived(false)
+    }
+
+    fn list_foods_by_archived(&self, archived: bool) -> Fallible<Vec<FoodListEntry>> {
         let sql = "
             select
                 food_id, name, brand
             from
                 foods
+            where
+                archived = ?1
             order by
                 name;
         ";
         let mut stmt = self.conn.prepare(sql)?;
-        let rows = stmt.query_map([], |row| {
+        let rows = stmt.query_map(params![archived], |row| {
+            Ok(FoodListEntry {
+                food_id: row.get(0)?,
+                name: row.get(1)?,
+                brand: row.get(2)?,
+            })
+        })?;
+        let mut foods = Vec::new();
+        for food in rows {
+            foods.push(food?);
+        }
+        Ok(foods)
+    }
+
+    /// Return summary information for the foods that have not been archived
+    /// whose name or brand matches `query`, best matches first. Each word of
+    /// the query matches any word that starts with it. A query without words
+    /// matches every food.
+    pub fn search_foods(&self, query: &str) -> Fallible<Vec<FoodListEntry>> {
+        self.search_foods_by_archived(query, false)
+    }
+
+    /// Like [`Db::search_foods`], but for archived foods.
+    pub fn search_archived_foods(&self, query: &str) -> Fallible<Vec<FoodListEntry>> {
+        self.search_foods_by_archived(query, true)
+    }
+
+    fn search_foods_by_archived(
+        &self,
+        query: &str,
+        archived: bool,
+    ) -> Fallible<Vec<FoodListEntry>> {
+        let Some(query) = fts_query(query) else {
+            return self.list_foods_by_archived(archived);
+        };
+        // Matches on the name weigh more than matches on the brand.
+        let sql = "
+            select
+                f.food_id, f.name, f.brand
+            from
+                foods_fts
+            join
+                foods f on f.food_id = foods_fts.rowid
+            where
+                foods_fts match ?1
+                and f.archived = ?2
+            order by
+                bm25(foods_fts, 10.0, 1.0), f.name;
+        ";
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params![query, archived], |row| {
             Ok(FoodListEntry {
                 food_id: row.get(0)?,
                 name: row.get(1)?,
@@ -272,7 +744,8 @@ impl Db {
                 carbs,
                 carbs_sugars,
                 fibre,
-                sodium
+                sodium,
+                archived
             from
                 foods
             where
@@ -295,12 +768,17 @@ impl Db {
                 carbs_sugars: row.get(9)?,
                 fibre: row.get(10)?,
                 sodium: row.get(11)?,
+                archived: row.get(12)?,
             })
         })?;
         Ok(entry)
     }
 
+    /// Edit a food. If the food is a recipe, the nutrition values in the input
+    /// are ignored, since they are derived from the ingredients.
     pub fn edit_food(&self, input: EditFoodInput) -> Fallible<()> {
+        let food_id = input.food_id;
+        let tx = self.conn.unchecked_transaction()?;
         let sql = "
             update foods
             set
@@ -335,87 +813,1038 @@ impl Db {
                 input.food_id,
             ],
         )?;
+        self.refresh_food(food_id)?;
+        if let Some(date) = input.correct_from {
+            self.correct_entries(food_id, date)?;
+        }
+        if let Some(serving) = input.serving {
+            self.create_serving(ServingInput {
+                food_id,
+                serving_name: serving.serving_name,
+                serving_amount: serving.serving_amount,
+                created_at: serving.created_at,
+            })?;
+        }
+        tx.commit()?;
         Ok(())
     }
 
-    pub fn create_serving(&self, input: ServingInput) -> Fallible<ServingId> {
-        let sql = "
-            insert into serving_sizes
-                (food_id, serving_name, serving_amount, created_at)
-            values
-                (?1, ?2, ?3, ?4)
-            returning serving_id;
-        ";
-        let serving_id: i64 = self.conn.query_row(
-            sql,
-            params![
-                input.food_id,
-                input.serving_name,
-                input.serving_amount,
-                input.created_at,
-            ],
-            |row| row.get(0),
-        )?;
-        Ok(serving_id)
-    }
-
-    pub fn delete_serving(&self, serving_id: ServingId) -> Fallible<()> {
-        let sql = "delete from serving_sizes where serving_id = ?1;";
-        self.conn.execute(sql, params![serving_id])?;
+    pub fn set_food_archived(&self, food_id: FoodId, archived: bool) -> Fallible<()> {
+        let sql = "update foods set archived = ?1 where food_id = ?2;";
+        self.conn.execute(sql, params![archived, food_id])?;
         Ok(())
     }
 
-    pub fn list_servings(&self, food_id: FoodId) -> Fallible<Vec<Serving>> {
+    pub fn get_food_usage(&self, food_id: FoodId) -> Fallible<FoodUsage> {
         let sql = "
             select
-                serving_id, serving_name, serving_amount
-            from
-                serving_sizes
-            where
-                food_id = ?1
-            order by
-                serving_name;
+                (select count(*) from entries where food_id = ?1),
+                (select count(distinct recipe_id) from recipe_ingredients where food_id = ?1),
+                (select count(distinct template_id) from meal_template_lines where food_id = ?1);
         ";
-        let mut stmt = self.conn.prepare(sql)?;
-        let rows = stmt.query_map(params![food_id], |row| {
-            Ok(Serving {
-                serving_id: row.get(0)?,
-                serving_name: row.get(1)?,
-                serving_amount: row.get(2)?,
+        let usage = self.conn.query_row(sql, params![food_id], |row| {
+            Ok(FoodUsage {
+                entries: row.get(0)?,
+                recipes: row.get(1)?,
+                templates: row.get(2)?,
             })
         })?;
-        let mut servings = Vec::new();
-        for serving in rows {
-            servings.push(serving?);
+        Ok(usage)
+    }
+
+    /// Delete a food, along with its servings, the entries that log it, and
+    /// its lines in meal templates. Fails if the food is an ingredient in a
+    /// recipe.
+    pub fn delete_food(&self, food_id: FoodId) -> Fallible<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        if self.get_food_usage(food_id)?.recipes > 0 {
+            return Err(AppError::conflict(
+                "Can't delete this food: it is an ingredient in a recipe.",
+            ));
         }
-        Ok(servings)
+        // Entries and template lines have to go before the servings they
+        // reference.
+        let sql = "delete from entries where food_id = ?1;";
+        self.conn.execute(sql, params![food_id])?;
+        let sql = "delete from meal_template_lines where food_id = ?1;";
+        self.conn.execute(sql, params![food_id])?;
+        let sql = "delete from foods where food_id = ?1;";
+        self.conn.execute(sql, params![food_id])?;
+        tx.commit()?;
+        Ok(())
     }
 
-    pub fn get_serving_by_id(&self, serving_id: ServingId) -> Fallible<Serving> {
+    /// Update the nutrition of the entries for a food, on or after the given
+    /// date, to the food's current values.
+    fn correct_entries(&self, food_id: FoodId, from: Date) -> Fallible<()> {
         let sql = "
-            select
-                serving_name, serving_amount
-            from
-                serving_sizes
+            update entries
+            set
+                (energy, protein, fat, fat_saturated, carbs, carbs_sugars, fibre, sodium) = (
+                    select
+                        energy, protein, fat, fat_saturated, carbs, carbs_sugars, fibre, sodium
+                    from
+                        foods
+                    where
+                        foods.food_id = entries.food_id
+                )
             where
-                serving_id = ?1;
+                food_id = ?1 and date >= ?2;
         ";
-        let serving: Serving = self.conn.query_row(sql, params![serving_id], |row| {
-            Ok(Serving {
-                serving_id,
-                serving_name: row.get(0)?,
-                serving_amount: row.get(1)?,
-            })
+        self.conn.execute(sql, params![food_id, from])?;
+        Ok(())
+    }
+
+    /// Create a new recipe, with no ingredients.
+    pub fn create_recipe(&self, input: CreateRecipeInput) -> Fallible<FoodId> {
+        let tx = self.conn.unchecked_transaction()?;
+        let food_id = self.insert_food(&CreateFoodInput {
+            name: input.name,
+            brand: input.brand,
+            serving_unit: input.serving_unit,
+            energy: 0.0,
+            protein: 0.0,
+            fat: 0.0,
+            fat_saturated: 0.0,
+            carbs: 0.0,
+            carbs_sugars: 0.0,
+            fibre: 0.0,
+            sodium: 0.0,
+            serving: None,
+            created_at: input.created_at,
         })?;
-        Ok(serving)
+        let sql = "insert into recipes (food_id, cooked_weight) values (?1, ?2);";
+        self.conn
+            .execute(sql, params![food_id, input.cooked_weight])?;
+        tx.commit()?;
+        Ok(food_id)
     }
 
-    pub fn create_entry(&self, input: CreateEntryInput) -> Fallible<EntryId> {
-        let sql = "
-            insert into entries
-                (date, food_id, serving_id, amount, created_at)
+    /// Return the recipe data for a food, or `None` if it's not a recipe.
+    pub fn get_recipe(&self, food_id: FoodId) -> Fallible<Option<Recipe>> {
+        let sql = "select cooked_weight from recipes where food_id = ?1;";
+        let recipe = self
+            .conn
+            .query_row(sql, params![food_id], |row| {
+                Ok(Recipe {
+                    food_id,
+                    cooked_weight: row.get(0)?,
+                })
+            })
+            .optional()?;
+        Ok(recipe)
+    }
+
+    pub fn edit_recipe(&self, input: EditRecipeInput) -> Fallible<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        let sql = "
+            update foods
+            set
+                name = ?1,
+                brand = ?2,
+                serving_unit = ?3
+            where
+                food_id = ?4;
+        ";
+        self.conn.execute(
+            sql,
+            params![
+                input.name,
+                input.brand,
+                input.serving_unit.as_str(),
+                input.food_id,
+            ],
+        )?;
+        let sql = "update recipes set cooked_weight = ?1 where food_id = ?2;";
+        self.conn
+            .execute(sql, params![input.cooked_weight, input.food_id])?;
+        self.refresh_food(input.food_id)?;
+        if let Some(date) = input.correct_from {
+            self.correct_entries(input.food_id, date)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Add an ingredient to a recipe. Fails if the ingredient is the recipe
+    /// itself, or a recipe that (directly or indirectly) contains it, or if
+    /// the serving belongs to a different food.
+    pub fn create_ingredient(&self, input: CreateIngredientInput) -> Fallible<IngredientId> {
+        let tx = self.conn.unchecked_transaction()?;
+        if self.get_recipe(input.recipe_id)?.is_none() {
+            return Err(AppError::bad_request("This food is not a recipe."));
+        }
+        self.check_serving(input.food_id, input.serving_id)?;
+        if input.food_id == input.recipe_id || self.contains(input.food_id, input.recipe_id)? {
+            return Err(AppError::conflict(
+                "Can't add this ingredient: it would make the recipe contain itself.",
+            ));
+        }
+        let sql = "
+            insert into recipe_ingredients
+                (recipe_id, food_id, serving_id, amount, created_at)
+            values
+                (?1, ?2, ?3, ?4, ?5)
+            returning ingredient_id;
+        ";
+        let ingredient_id: i64 = self.conn.query_row(
+            sql,
+            params![
+                input.recipe_id,
+                input.food_id,
+                input.serving_id,
+                input.amount,
+                input.created_at,
+            ],
+            |row| row.get(0),
+        )?;
+        self.refresh_food(input.recipe_id)?;
+        tx.commit()?;
+        Ok(ingredient_id)
+    }
+
+    /// Remove an ingredient from a recipe. Fails with not found if the
+    /// ingredient belongs to a different recipe.
+    pub fn delete_ingredient(
+        &self,
+        recipe_id: FoodId,
+        ingredient_id: IngredientId,
+    ) -> Fallible<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        let sql = "delete from recipe_ingredients where ingredient_id = ?1 and recipe_id = ?2;";
+        let deleted = self.conn.execute(sql, params![ingredient_id, recipe_id])?;
+        if deleted == 0 {
+            return Err(AppError::not_found("Ingredient not found."));
+        }
+        self.refresh_food(recipe_id)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn list_ingredients(&self, recipe_id: FoodId) -> Fallible<Vec<Ingredient>> {
+        let sql = "
+            select
+                i.ingredient_id,
+                i.food_id,
+                f.name,
+                f.brand,
+                f.serving_unit,
+                s.serving_name,
+                s.serving_amount,
+                i.amount,
+                f.energy,
+                f.protein,
+                f.fat,
+                f.fat_saturated,
+                f.carbs,
+                f.carbs_sugars,
+                f.fibre,
+                f.sodium
+            from
+                recipe_ingredients i
+            inner join
+                foods f on f.food_id = i.food_id
+            left join
+                serving_sizes s on s.serving_id = i.serving_id
+            where
+                i.recipe_id = ?1
+            order by
+                i.ingredient_id;
+        ";
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params![recipe_id], |row| {
+            let serving_unit_str: String = row.get(4)?;
+            let serving_unit = BasicUnit::try_from(serving_unit_str.as_str())
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+            let serving_amount: Option<f64> = row.get(6)?;
+            let amount: f64 = row.get(7)?;
+            let per_100 = Nutrition {
+                energy: row.get(8)?,
+                protein: row.get(9)?,
+                fat: row.get(10)?,
+                fat_saturated: row.get(11)?,
+                carbs: row.get(12)?,
+                carbs_sugars: row.get(13)?,
+                fibre: row.get(14)?,
+                sodium: row.get(15)?,
+            };
+            // Get the amount of the ingredient in its base unit.
+            let amount_base: f64 = amount * serving_amount.unwrap_or(1.0);
+            Ok(Ingredient {
+                ingredient_id: row.get(0)?,
+                food_id: row.get(1)?,
+                name: row.get(2)?,
+                brand: row.get(3)?,
+                serving_unit,
+                serving_name: row.get(5)?,
+                amount,
+                nutrition: per_100.scale(amount_base / 100.0),
+            })
+        })?;
+        let mut ingredients = Vec::new();
+        for ingredient in rows {
+            ingredients.push(ingredient?);
+        }
+        Ok(ingredients)
+    }
+
+    /// Whether the recipe `recipe_id` contains the food `food_id`, directly or
+    /// through a nested recipe.
+    fn contains(&self, recipe_id: FoodId, food_id: FoodId) -> Fallible<bool> {
+        let sql = "
+            with recursive contents(food_id) as (
+                select food_id from recipe_ingredients where recipe_id = ?1
+                union
+                select i.food_id from recipe_ingredients i
+                inner join contents c on i.recipe_id = c.food_id
+            )
+            select exists(select 1 from contents where food_id = ?2);
+        ";
+        let contains: bool = self
+            .conn
+            .query_row(sql, params![recipe_id, food_id], |row| row.get(0))?;
+        Ok(contains)
+    }
+
+    /// Bring derived nutrition data up to date after a food has changed: if
+    /// the food is a recipe, recompute its nutrition from its ingredients, then
+    /// do the same for every recipe that uses it as an ingredient.
+    ///
+    /// Must be called inside a transaction.
+    fn refresh_food(&self, food_id: FoodId) -> Fallible<()> {
+        if let Some(recipe) = self.get_recipe(food_id)? {
+            let mut total = Nutrition::zero();
+            for ingredient in self.list_ingredients(food_id)? {
+                total = total + ingredient.nutrition;
+            }
+            let n = total.scale(100.0 / recipe.cooked_weight);
+            let sql = "
+                update foods
+                set
+                    energy = ?1,
+                    protein = ?2,
+                    fat = ?3,
+                    fat_saturated = ?4,
+                    carbs = ?5,
+                    carbs_sugars = ?6,
+                    fibre = ?7,
+                    sodium = ?8
+                where
+                    food_id = ?9;
+            ";
+            self.conn.execute(
+                sql,
+                params![
+                    n.energy,
+                    n.protein,
+                    n.fat,
+                    n.fat_saturated,
+                    n.carbs,
+                    n.carbs_sugars,
+                    n.fibre,
+                    n.sodium,
+                    food_id,
+                ],
+            )?;
+        }
+        // Recipes can't contain themselves, so this terminates.
+        let sql = "select distinct recipe_id from recipe_ingredients where food_id = ?1;";
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params![food_id], |row| row.get(0))?;
+        let mut dependents: Vec<FoodId> = Vec::new();
+        for row in rows {
+            dependents.push(row?);
+        }
+        for recipe_id in dependents {
+            self.refresh_food(recipe_id)?;
+        }
+        Ok(())
+    }
+
+    pub fn create_serving(&self, input: ServingInput) -> Fallible<ServingId> {
+        let sql = "
+            insert into serving_sizes
+                (food_id, serving_name, serving_amount, created_at)
+            values
+                (?1, ?2, ?3, ?4)
+            returning serving_id;
+        ";
+        let serving_id: i64 = self.conn.query_row(
+            sql,
+            params![
+                input.food_id,
+                input.serving_name,
+                input.serving_amount,
+                input.created_at,
+            ],
+            |row| row.get(0),
+        )?;
+        Ok(serving_id)
+    }
+
+    /// Delete a serving. Entries and recipe ingredients that use it are
+    /// converted to amounts in the food's base unit first.
+    pub fn delete_serving(&self, serving_id: ServingId) -> Fallible<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        let sql = "
+            update entries
+            set
+                amount = amount * (select serving_amount from serving_sizes where serving_id = ?1),
+                serving_id = null
+            where
+                serving_id = ?1;
+        ";
+        self.conn.execute(sql, params![serving_id])?;
+        let sql = "
+            update recipe_ingredients
+            set
+                amount = amount * (select serving_amount from serving_sizes where serving_id = ?1),
+                serving_id = null
+            where
+                serving_id = ?1;
+        ";
+        self.conn.execute(sql, params![serving_id])?;
+        let sql = "
+            update meal_template_lines
+            set
+                amount = amount * (select serving_amount from serving_sizes where serving_id = ?1),
+                serving_id = null
+            where
+                serving_id = ?1;
+        ";
+        self.conn.execute(sql, params![serving_id])?;
+        let sql = "delete from serving_sizes where serving_id = ?1;";
+        self.conn.execute(sql, params![serving_id])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn get_serving_usage(&self, serving_id: ServingId) -> Fallible<ServingUsage> {
+        let sql = "
+            select
+                (select count(*) from entries where serving_id = ?1),
+                (select count(*) from recipe_ingredients where serving_id = ?1),
+                (select count(*) from meal_template_lines where serving_id = ?1);
+        ";
+        let usage = self.conn.query_row(sql, params![serving_id], |row| {
+            Ok(ServingUsage {
+                entries: row.get(0)?,
+                ingredients: row.get(1)?,
+                template_lines: row.get(2)?,
+            })
+        })?;
+        Ok(usage)
+    }
+
+    pub fn list_servings(&self, food_id: FoodId) -> Fallible<Vec<Serving>> {
+        let sql = "
+            select
+                serving_id, serving_name, serving_amount
+            from
+                serving_sizes
+            where
+                food_id = ?1
+            order by
+                serving_name;
+        ";
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params![food_id], |row| {
+            Ok(Serving {
+                serving_id: row.get(0)?,
+                serving_name: row.get(1)?,
+                serving_amount: row.get(2)?,
+            })
+        })?;
+        let mut servings = Vec::new();
+        for serving in rows {
+            servings.push(serving?);
+        }
+        Ok(servings)
+    }
+
+    /// Return a serving of a food. Fails with not found if the serving
+    /// belongs to a different food.
+    pub fn get_serving(&self, food_id: FoodId, serving_id: ServingId) -> Fallible<Serving> {
+        let sql = "
+            select
+                serving_name, serving_amount
+            from
+                serving_sizes
+            where
+                serving_id = ?1
+                and food_id = ?2;
+        ";
+        let serving: Option<Serving> = self
+            .conn
+            .query_row(sql, params![serving_id, food_id], |row| {
+                Ok(Serving {
+                    serving_id,
+                    serving_name: row.get(0)?,
+                    serving_amount: row.get(1)?,
+                })
+            })
+            .optional()?;
+        serving.ok_or_else(|| AppError::not_found("Serving not found."))
+    }
+
+    /// Fail unless the serving, if any, belongs to the given food.
+    fn check_serving(&self, food_id: FoodId, serving_id: Option<ServingId>) -> Fallible<()> {
+        if let Some(serving_id) = serving_id {
+            let sql = "select food_id from serving_sizes where serving_id = ?1;";
+            let serving_food_id: FoodId = self
+                .conn
+                .query_row(sql, params![serving_id], |row| row.get(0))?;
+            if serving_food_id != food_id {
+                return Err(AppError::bad_request(
+                    "The serving does not belong to this food.",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn create_meal_slot(&self, input: CreateMealSlotInput) -> Fallible<SlotId> {
+        let sql = "
+            insert into meal_slots
+                (name, starts_at, created_at)
+            values
+                (?1, ?2, ?3)
+            returning slot_id;
+        ";
+        let slot_id: i64 = self.conn.query_row(
+            sql,
+            params![input.name, input.starts_at, input.created_at],
+            |row| row.get(0),
+        )?;
+        Ok(slot_id)
+    }
+
+    /// Delete a meal slot. Its entries are left without a slot.
+    pub fn delete_meal_slot(&self, slot_id: SlotId) -> Fallible<()> {
+        let sql = "delete from meal_slots where slot_id = ?1;";
+        self.conn.execute(sql, params![slot_id])?;
+        Ok(())
+    }
+
+    /// Return all meal slots in the order they happen in the day. Slots
+    /// without a start time come last.
+    pub fn list_meal_slots(&self) -> Fallible<Vec<MealSlot>> {
+        let sql = "
+            select
+                slot_id, name, starts_at
+            from
+                meal_slots
+            order by
+                starts_at is null, starts_at, name;
+        ";
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map([], |row| {
+            Ok(MealSlot {
+                slot_id: row.get(0)?,
+                name: row.get(1)?,
+                starts_at: row.get(2)?,
+            })
+        })?;
+        let mut slots = Vec::new();
+        for slot in rows {
+            slots.push(slot?);
+        }
+        Ok(slots)
+    }
+
+    pub fn create_target_profile(&self, input: CreateTargetProfileInput) -> Fallible<ProfileId> {
+        let sql = "
+            insert into target_profiles
+                (name, created_at)
+            values
+                (?1, ?2)
+            returning profile_id;
+        ";
+        let profile_id: i64 =
+            self.conn
+                .query_row(sql, params![input.name, input.created_at], |row| row.get(0))?;
+        Ok(profile_id)
+    }
+
+    /// Delete a target profile, along with its targets. Days it was assigned
+    /// to fall back to the default profile.
+    pub fn delete_target_profile(&self, profile_id: ProfileId) -> Fallible<()> {
+        if profile_id == DEFAULT_PROFILE_ID {
+            return Err(AppError::conflict("The default profile can't be deleted."));
+        }
+        let sql = "delete from target_profiles where profile_id = ?1;";
+        self.conn.execute(sql, params![profile_id])?;
+        Ok(())
+    }
+
+    /// Return all target profiles, the default one first.
+    pub fn list_target_profiles(&self) -> Fallible<Vec<TargetProfile>> {
+        let sql = "
+            select
+                profile_id, name
+            from
+                target_profiles
+            order by
+                profile_id;
+        ";
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map([], |row| {
+            Ok(TargetProfile {
+                profile_id: row.get(0)?,
+                name: row.get(1)?,
+            })
+        })?;
+        let mut profiles = Vec::new();
+        for profile in rows {
+            profiles.push(profile?);
+        }
+        Ok(profiles)
+    }
+
+    /// Create an empty meal template.
+    pub fn create_meal_template(&self, input: CreateMealTemplateInput) -> Fallible<TemplateId> {
+        let sql = "
+            insert into meal_templates
+                (name, created_at)
+            values
+                (?1, ?2)
+            returning template_id;
+        ";
+        let template_id: i64 =
+            self.conn
+                .query_row(sql, params![input.name, input.created_at], |row| row.get(0))?;
+        Ok(template_id)
+    }
+
+    /// Create a meal template with a line for each of the given entries, in
+    /// the order they were eaten. Quick-add entries are left out, since they
+    /// have no food.
+    pub fn create_meal_template_from_entries(
+        &self,
+        input: CreateMealTemplateInput,
+        entry_ids: &[EntryId],
+    ) -> Fallible<TemplateId> {
+        let tx = self.conn.unchecked_transaction()?;
+        let created_at = input.created_at;
+        let template_id = self.create_meal_template(input)?;
+        let sql = "
+            insert into meal_template_lines
+                (template_id, food_id, serving_id, amount, created_at)
+            select
+                ?1, food_id, serving_id, amount, ?2
+            from
+                entries
+            where
+                entry_id = ?3
+                and food_id is not null;
+        ";
+        let mut entries: Vec<Entry> = Vec::new();
+        for entry_id in entry_ids {
+            entries.push(self.get_entry(*entry_id)?);
+        }
+        entries.sort_by_key(|entry| (entry.date, entry.eaten_at, entry.entry_id));
+        for entry in &entries {
+            self.conn
+                .execute(sql, params![template_id, created_at, entry.entry_id])?;
+        }
+        tx.commit()?;
+        Ok(template_id)
+    }
+
+    /// Delete a meal template, along with its lines.
+    pub fn delete_meal_template(&self, template_id: TemplateId) -> Fallible<()> {
+        let sql = "delete from meal_templates where template_id = ?1;";
+        self.conn.execute(sql, params![template_id])?;
+        Ok(())
+    }
+
+    pub fn get_meal_template(&self, template_id: TemplateId) -> Fallible<MealTemplate> {
+        let sql = "select template_id, name from meal_templates where template_id = ?1;";
+        let template = self.conn.query_row(sql, params![template_id], |row| {
+            Ok(MealTemplate {
+                template_id: row.get(0)?,
+                name: row.get(1)?,
+            })
+        })?;
+        Ok(template)
+    }
+
+    /// Return all meal templates, ordered by name.
+    pub fn list_meal_templates(&self) -> Fallible<Vec<MealTemplate>> {
+        let sql = "
+            select
+                template_id, name
+            from
+                meal_templates
+            order by
+                name;
+        ";
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map([], |row| {
+            Ok(MealTemplate {
+                template_id: row.get(0)?,
+                name: row.get(1)?,
+            })
+        })?;
+        let mut templates = Vec::new();
+        for template in rows {
+            templates.push(template?);
+        }
+        Ok(templates)
+    }
+
+    /// Add a line to a meal template. Fails if the serving belongs to a
+    /// different food.
+    pub fn create_template_line(&self, input: CreateTemplateLineInput) -> Fallible<TemplateLineId> {
+        let tx = self.conn.unchecked_transaction()?;
+        self.check_serving(input.food_id, input.serving_id)?;
+        let sql = "
+            insert into meal_template_lines
+                (template_id, food_id, serving_id, amount, created_at)
             values
                 (?1, ?2, ?3, ?4, ?5)
+            returning line_id;
+        ";
+        let line_id: i64 = self.conn.query_row(
+            sql,
+            params![
+                input.template_id,
+                input.food_id,
+                input.serving_id,
+                input.amount,
+                input.created_at,
+            ],
+            |row| row.get(0),
+        )?;
+        tx.commit()?;
+        Ok(line_id)
+    }
+
+    /// Remove a line from a meal template. Fails with not found if the line
+    /// belongs to a different template.
+    pub fn delete_template_line(
+        &self,
+        template_id: TemplateId,
+        line_id: TemplateLineId,
+    ) -> Fallible<()> {
+        let sql = "delete from meal_template_lines where line_id = ?1 and template_id = ?2;";
+        let deleted = self.conn.execute(sql, params![line_id, template_id])?;
+        if deleted == 0 {
+            return Err(AppError::not_found("Template line not found."));
+        }
+        Ok(())
+    }
+
+    pub fn list_template_lines(&self, template_id: TemplateId) -> Fallible<Vec<TemplateLine>> {
+        let sql = "
+            select
+                l.line_id,
+                l.food_id,
+                f.name,
+                f.brand,
+                f.serving_unit,
+                l.serving_id,
+                s.serving_name,
+                l.amount
+            from
+                meal_template_lines l
+            inner join
+                foods f on f.food_id = l.food_id
+            left join
+                serving_sizes s on s.serving_id = l.serving_id
+            where
+                l.template_id = ?1
+            order by
+                l.line_id;
+        ";
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params![template_id], |row| {
+            let serving_unit_str: String = row.get(4)?;
+            let serving_unit = BasicUnit::try_from(serving_unit_str.as_str())
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+            Ok(TemplateLine {
+                line_id: row.get(0)?,
+                food_id: row.get(1)?,
+                name: row.get(2)?,
+                brand: row.get(3)?,
+                serving_unit,
+                serving_id: row.get(5)?,
+                serving_name: row.get(6)?,
+                amount: row.get(7)?,
+            })
+        })?;
+        let mut lines = Vec::new();
+        for line in rows {
+            lines.push(line?);
+        }
+        Ok(lines)
+    }
+
+    /// Log every line of a meal template, and return how many entries were
+    /// created.
+    pub fn log_meal_template(&self, input: LogMealTemplateInput) -> Fallible<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let lines: Vec<TemplateLine> = self.list_template_lines(input.template_id)?;
+        for line in &lines {
+            self.create_entry(CreateEntryInput {
+                date: input.date,
+                food_id: line.food_id,
+                serving_id: line.serving_id,
+                amount: line.amount * input.factor,
+                eaten_at: input.eaten_at,
+                slot_id: input.slot_id,
+                created_at: input.created_at,
+            })?;
+        }
+        tx.commit()?;
+        Ok(lines.len())
+    }
+
+    /// Return the profile assigned to each day of the week, starting on
+    /// Monday. `None` for days that use the default profile.
+    pub fn list_weekday_profiles(&self) -> Fallible<[Option<ProfileId>; 7]> {
+        let sql = "select weekday, profile_id from profile_weekdays;";
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map([], |row| {
+            let weekday: usize = row.get(0)?;
+            let profile_id: ProfileId = row.get(1)?;
+            Ok((weekday, profile_id))
+        })?;
+        let mut weekdays = [None; 7];
+        for row in rows {
+            let (weekday, profile_id) = row?;
+            weekdays[weekday] = Some(profile_id);
+        }
+        Ok(weekdays)
+    }
+
+    /// Assign a profile to each day of the week, starting on Monday, in a
+    /// single transaction. `None` means the default profile.
+    pub fn set_weekday_profiles(&self, profiles: &[Option<ProfileId>; 7]) -> Fallible<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for (weekday, profile_id) in profiles.iter().enumerate() {
+            match profile_id {
+                Some(profile_id) => {
+                    let sql = "
+                        insert into profile_weekdays (weekday, profile_id)
+                        values (?1, ?2)
+                        on conflict (weekday) do update set profile_id = excluded.profile_id;
+                    ";
+                    self.conn.execute(sql, params![weekday, profile_id])?;
+                }
+                None => {
+                    let sql = "delete from profile_weekdays where weekday = ?1;";
+                    self.conn.execute(sql, params![weekday])?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Choose the profile for a specific date, overriding the weekday rules.
+    /// `None` removes the override.
+    pub fn set_profile_override(&self, date: Date, profile_id: Option<ProfileId>) -> Fallible<()> {
+        match profile_id {
+            Some(profile_id) => {
+                let sql = "
+                    insert into profile_overrides (date, profile_id)
+                    values (?1, ?2)
+                    on conflict (date) do update set profile_id = excluded.profile_id;
+                ";
+                self.conn.execute(sql, params![date, profile_id])?;
+            }
+            None => {
+                let sql = "delete from profile_overrides where date = ?1;";
+                self.conn.execute(sql, params![date])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the profile that applies on the given date: the one chosen for
+    /// the date, else the one assigned to its weekday, else the default.
+    pub fn get_day_profile(&self, date: Date) -> Fallible<DayProfile> {
+        let sql = "
+            select
+                p.profile_id, p.name, o.profile_id is not null
+            from
+                target_profiles p
+            left join
+                profile_overrides o on o.date = ?1
+            where
+                p.profile_id = coalesce(
+                    o.profile_id,
+                    (select profile_id from profile_weekdays where weekday = ?2),
+                    ?3
+                );
+        ";
+        let day_profile = self.conn.query_row(
+            sql,
+            params![date, date.weekday(), DEFAULT_PROFILE_ID],
+            |row| {
+                Ok(DayProfile {
+                    profile: TargetProfile {
+                        profile_id: row.get(0)?,
+                        name: row.get(1)?,
+                    },
+                    overridden: row.get(2)?,
+                })
+            },
+        )?;
+        Ok(day_profile)
+    }
+
+    /// Set the targets for some nutrients in a profile, from the given dates
+    /// on, in a single transaction. Replaces any target set for a nutrient on
+    /// that same date.
+    pub fn set_targets(&self, inputs: Vec<SetTargetInput>) -> Fallible<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        let sql = "
+            insert into targets
+                (profile_id, nutrient, effective_from, min, max, created_at)
+            values
+                (?1, ?2, ?3, ?4, ?5, ?6)
+            on conflict (profile_id, nutrient, effective_from) do update set
+                min = excluded.min,
+                max = excluded.max;
+        ";
+        for input in inputs {
+            self.conn.execute(
+                sql,
+                params![
+                    input.profile_id,
+                    input.nutrient.as_str(),
+                    input.effective_from,
+                    input.min,
+                    input.max,
+                    input.created_at,
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Return the targets of a profile that apply on the given date.
+    pub fn get_targets(&self, profile_id: ProfileId, date: Date) -> Fallible<Vec<Target>> {
+        let sql = "
+            select
+                nutrient, effective_from, min, max
+            from
+                targets t
+            where
+                profile_id = ?1
+                and effective_from = (
+                    select max(effective_from) from targets
+                    where profile_id = ?1 and nutrient = t.nutrient and effective_from <= ?2
+                )
+                and (min is not null or max is not null);
+        ";
+        self.query_targets(sql, params![profile_id, date])
+    }
+
+    /// Return the targets that apply on each day from `start` to `end`, under
+    /// the profile in effect that day. Days without targets are left out.
+    pub fn list_day_targets(&self, start: Date, end: Date) -> Fallible<HashMap<Date, Vec<Target>>> {
+        let weekdays: [Option<ProfileId>; 7] = self.list_weekday_profiles()?;
+        let sql = "select date, profile_id from profile_overrides where date between ?1 and ?2;";
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params![start, end], |row| {
+            let date: Date = row.get(0)?;
+            let profile_id: ProfileId = row.get(1)?;
+            Ok((date, profile_id))
+        })?;
+        let mut overrides: HashMap<Date, ProfileId> = HashMap::new();
+        for row in rows {
+            let (date, profile_id) = row?;
+            overrides.insert(date, profile_id);
+        }
+        // Every target that can apply in the range, oldest first.
+        let sql = "
+            select
+                nutrient, effective_from, min, max, profile_id
+            from
+                targets
+            where
+                effective_from <= ?1
+            order by
+                effective_from, target_id;
+        ";
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params![end], |row| {
+            let profile_id: ProfileId = row.get(4)?;
+            Ok((profile_id, read_target(row)?))
+        })?;
+        let mut history: Vec<(ProfileId, Target)> = Vec::new();
+        for row in rows {
+            history.push(row?);
+        }
+        let mut days: HashMap<Date, Vec<Target>> = HashMap::new();
+        for offset in 0..=start.days_until(end) {
+            let date = start.add_days(offset);
+            let profile_id: ProfileId = overrides
+                .get(&date)
+                .copied()
+                .or(weekdays[date.weekday() as usize])
+                .unwrap_or(DEFAULT_PROFILE_ID);
+            let mut targets: Vec<Target> = Vec::new();
+            for nutrient in Nutrient::ALL {
+                // The most recent target set on or before the date.
+                let target = history.iter().rev().find(|(p, target)| {
+                    *p == profile_id && target.nutrient == nutrient && target.effective_from <= date
+                });
+                if let Some((_, target)) = target
+                    && (target.min.is_some() || target.max.is_some())
+                {
+                    targets.push(target.clone());
+                }
+            }
+            if !targets.is_empty() {
+                days.insert(date, targets);
+            }
+        }
+        Ok(days)
+    }
+
+    /// Return every target ever set in a profile, most recent first.
+    pub fn list_targets(&self, profile_id: ProfileId) -> Fallible<Vec<Target>> {
+        let sql = "
+            select
+                nutrient, effective_from, min, max
+            from
+                targets
+            where
+                profile_id = ?1
+            order by
+                effective_from desc, target_id;
+        ";
+        self.query_targets(sql, params![profile_id])
+    }
+
+    fn query_targets(&self, sql: &str, params: impl Params) -> Fallible<Vec<Target>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params, read_target)?;
+        let mut targets = Vec::new();
+        for target in rows {
+            targets.push(target?);
+        }
+        Ok(targets)
+    }
+
+    /// Log an entry. The food's current nutrition is recorded with the entry.
+    /// Fails if the serving belongs to a different food.
+    pub fn create_entry(&self, input: CreateEntryInput) -> Fallible<EntryId> {
+        self.check_serving(input.food_id, input.serving_id)?;
+        let sql = "
+            insert into entries
+                (date, food_id, serving_id, amount, eaten_at, slot_id, created_at, energy, protein, fat, fat_saturated, carbs, carbs_sugars, fibre, sodium)
+            select
+                ?1, food_id, ?3, ?4, ?5, ?6, ?7, energy, protein, fat, fat_saturated, carbs, carbs_sugars, fibre, sodium
+            from
+                foods
+            where
+                food_id = ?2
             returning entry_id;
         ";
         let entry_id: i64 = self.conn.query_row(
@@ -425,6 +1854,8 @@ impl Db {
                 input.food_id,
                 input.serving_id,
                 input.amount,
+                input.eaten_at,
+                input.slot_id,
                 input.created_at,
             ],
             |row| row.get(0),
@@ -438,25 +1869,270 @@ impl Db {
         Ok(())
     }
 
-    pub fn list_entries(&self, date: Date) -> Fallible<Vec<Entry>> {
+    /// Log a quick-add entry.
+    pub fn create_quick_entry(&self, input: CreateQuickEntryInput) -> Fallible<EntryId> {
+        let sql = "
+            insert into entries
+                (date, label, amount, eaten_at, slot_id, created_at, energy, protein, fat, fat_saturated, carbs, carbs_sugars, fibre, sodium)
+            values
+                (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+            returning entry_id;
+        ";
+        let n = input.nutrition;
+        let entry_id: i64 = self.conn.query_row(
+            sql,
+            params![
+                input.date,
+                input.label,
+                QUICK_ADD_AMOUNT,
+                input.eaten_at,
+                input.slot_id,
+                input.created_at,
+                n.energy,
+                n.protein,
+                n.fat,
+                n.fat_saturated,
+                n.carbs,
+                n.carbs_sugars,
+                n.fibre,
+                n.sodium,
+            ],
+            |row| row.get(0),
+        )?;
+        Ok(entry_id)
+    }
+
+    /// Turn a quick-add entry into one serving of a new food, whose nutrition
+    /// is derived from the entry's. The entry's nutrition doesn't change.
+    pub fn convert_quick_entry(&self, input: ConvertQuickEntryInput) -> Fallible<FoodId> {
+        let tx = self.conn.unchecked_transaction()?;
+        let entry: Entry = self.get_entry(input.entry_id)?;
+        if entry.food_id.is_some() {
+            return Err(AppError::conflict("This entry is already of a food."));
+        }
+        let per_100: Nutrition = entry.food_nutrition.scale(100.0 / input.serving_amount);
+        let food_id = self.insert_food(&CreateFoodInput {
+            name: input.name,
+            brand: input.brand,
+            serving_unit: input.serving_unit,
+            energy: per_100.energy,
+            protein: per_100.protein,
+            fat: per_100.fat,
+            fat_saturated: per_100.fat_saturated,
+            carbs: per_100.carbs,
+            carbs_sugars: per_100.carbs_sugars,
+            fibre: per_100.fibre,
+            sodium: per_100.sodium,
+            serving: None,
+            created_at: input.created_at,
+        })?;
+        let serving_id = self.create_serving(ServingInput {
+            food_id,
+            serving_name: input.serving_name,
+            serving_amount: input.serving_amount,
+            created_at: input.created_at,
+        })?;
         let sql = "
+            update entries
+            set
+                food_id = ?2,
+                label = null,
+                serving_id = ?3,
+                -- One serving, instead of `QUICK_ADD_AMOUNT`, now that the
+                -- nutrition is per 100 of the food.
+                amount = 1,
+                energy = ?4,
+                protein = ?5,
+                fat = ?6,
+                fat_saturated = ?7,
+                carbs = ?8,
+                carbs_sugars = ?9,
+                fibre = ?10,
+                sodium = ?11
+            where
+                entry_id = ?1;
+        ";
+        self.conn.execute(
+            sql,
+            params![
+                input.entry_id,
+                food_id,
+                serving_id,
+                per_100.energy,
+                per_100.protein,
+                per_100.fat,
+                per_100.fat_saturated,
+                per_100.carbs,
+                per_100.carbs_sugars,
+                per_100.fibre,
+                per_100.sodium,
+            ],
+        )?;
+        tx.commit()?;
+        Ok(food_id)
+    }
+
+    /// Copy entries from one day to another, and return how many were copied.
+    /// The copies keep the food, serving, amount, time of day, meal and
+    /// nutrition of the originals.
+    pub fn copy_entries(&self, input: CopyEntriesInput) -> Fallible<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let entries: Vec<LogEntry> = self.list_log_entries(input.from, input.from)?;
+        let selected: Vec<EntryId> = entries
+            .iter()
+            .map(|e| &e.entry)
+            .filter(|entry| match &input.selection {
+                EntrySelection::All => true,
+                EntrySelection::Slot(slot_id) => entry.slot_id == *slot_id,
+                EntrySelection::Entries(entry_ids) => entry_ids.contains(&entry.entry_id),
+            })
+            .map(|entry| entry.entry_id)
+            .collect();
+        let sql = "
+            insert into entries
+                (date, food_id, label, serving_id, amount, eaten_at, slot_id, created_at, energy, protein, fat, fat_saturated, carbs, carbs_sugars, fibre, sodium)
             select
-                entry_id, food_id, serving_id, amount, created_at
+                ?2, food_id, label, serving_id, amount, eaten_at, slot_id, ?3, energy, protein, fat, fat_saturated, carbs, carbs_sugars, fibre, sodium
             from
                 entries
             where
-                date = ?1
+                entry_id = ?1;
+        ";
+        for entry_id in &selected {
+            self.conn
+                .execute(sql, params![entry_id, input.to, input.created_at])?;
+        }
+        tx.commit()?;
+        Ok(selected.len())
+    }
+
+    /// Edit an entry. If the food changes, the entry takes the current
+    /// nutrition of the new food.
+    pub fn edit_entry(&self, input: EditEntryInput) -> Fallible<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        self.check_serving(input.food_id, input.serving_id)?;
+        let old: Entry = self.get_entry(input.entry_id)?;
+        if old.food_id.is_none() {
+            return Err(AppError::bad_request(
+                "Quick-add entries can't be edited. Delete the entry and add it again instead.",
+            ));
+        }
+        let sql = "
+            update entries
+            set
+                date = ?1,
+                serving_id = ?2,
+                amount = ?3,
+                eaten_at = ?4,
+                slot_id = ?5
+            where
+                entry_id = ?6;
+        ";
+        self.conn.execute(
+            sql,
+            params![
+                input.date,
+                input.serving_id,
+                input.amount,
+                input.eaten_at,
+                input.slot_id,
+                input.entry_id,
+            ],
+        )?;
+        if old.food_id != Some(input.food_id) {
+            let sql = "
+                update entries
+                set
+                    food_id = ?1,
+                    (energy, protein, fat, fat_saturated, carbs, carbs_sugars, fibre, sodium) = (
+                        select
+                            energy, protein, fat, fat_saturated, carbs, carbs_sugars, fibre, sodium
+                        from
+                            foods
+                        where
+                            food_id = ?1
+                    )
+                where
+                    entry_id = ?2;
+            ";
+            self.conn
+                .execute(sql, params![input.food_id, input.entry_id])?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Move an entry to another meal.
+    pub fn set_entry_slot(&self, entry_id: EntryId, slot_id: Option<SlotId>) -> Fallible<()> {
+        let sql = "update entries set slot_id = ?1 where entry_id = ?2;";
+        self.conn.execute(sql, params![slot_id, entry_id])?;
+        Ok(())
+    }
+
+    pub fn get_entry(&self, entry_id: EntryId) -> Fallible<Entry> {
+        let sql = format!("select {ENTRY_COLUMNS} from entries where entry_id = ?1;");
+        let entry = self.conn.query_row(&sql, params![entry_id], read_entry)?;
+        Ok(entry)
+    }
+
+    /// Return the entries logged from `start` to `end`, inclusive, with
+    /// their food and serving data, in the order they were eaten.
+    pub fn list_log_entries(&self, start: Date, end: Date) -> Fallible<Vec<LogEntry>> {
+        let sql = "
+            select
+                e.entry_id,
+                e.date,
+                e.food_id,
+                e.label,
+                e.serving_id,
+                e.amount,
+                e.eaten_at,
+                e.slot_id,
+                e.energy,
+                e.protein,
+                e.fat,
+                e.fat_saturated,
+                e.carbs,
+                e.carbs_sugars,
+                e.fibre,
+                e.sodium,
+                coalesce(f.name, e.label),
+                coalesce(f.brand, ''),
+                f.serving_unit,
+                s.serving_name,
+                s.serving_amount
+            from
+                entries e
+            left join
+                foods f on f.food_id = e.food_id
+            left join
+                serving_sizes s on s.serving_id = e.serving_id
+            where
+                e.date between ?1 and ?2
             order by
-                created_at;
+                e.date, e.eaten_at, e.created_at;
         ";
         let mut stmt = self.conn.prepare(sql)?;
-        let rows = stmt.query_map(params![date], |row| {
-            Ok(Entry {
-                entry_id: row.get(0)?,
-                food_id: row.get(1)?,
-                serving_id: row.get(2)?,
-                amount: row.get(3)?,
-                created_at: row.get(4)?,
+        let rows = stmt.query_map(params![start, end], |row| {
+            let entry = read_entry(row)?;
+            let serving_unit_str: Option<String> = row.get(18)?;
+            let serving_unit = serving_unit_str
+                .map(|s| BasicUnit::try_from(s.as_str()))
+                .transpose()
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+            // Without a serving, the amount is already in the base unit.
+            // Quick-add entries have no serving and an amount of
+            // `QUICK_ADD_AMOUNT`, so they come out as stored.
+            let serving_amount: Option<f64> = row.get(20)?;
+            let base_amount = entry.amount * serving_amount.unwrap_or(1.0);
+            let nutrition = entry.food_nutrition.scale(base_amount / 100.0);
+            Ok(LogEntry {
+                entry,
+                food_name: row.get(16)?,
+                food_brand: row.get(17)?,
+                serving_unit,
+                serving_name: row.get(19)?,
+                nutrition,
             })
         })?;
         let mut entries = Vec::new();
@@ -465,4 +2141,303 @@ impl Db {
         }
         Ok(entries)
     }
+
+    /// Return the total nutrition of each day between `start` and `end`,
+    /// inclusive, in date order. Days without entries are left out.
+    pub fn list_daily_totals(&self, start: Date, end: Date) -> Fallible<Vec<DailyTotals>> {
+        let sql = "
+            with scaled as (
+                select
+                    e.date,
+                    -- 1 for quick-add entries, see `QUICK_ADD_AMOUNT`.
+                    e.amount * coalesce(s.serving_amount, 1) / 100 as factor,
+                    e.energy,
+                    e.protein,
+                    e.fat,
+                    e.fat_saturated,
+                    e.carbs,
+                    e.carbs_sugars,
+                    e.fibre,
+                    e.sodium
+                from
+                    entries e
+                left join
+                    serving_sizes s on s.serving_id = e.serving_id
+                where
+                    e.date between ?1 and ?2
+            )
+            select
+                date,
+                sum(factor * energy),
+                sum(factor * protein),
+                sum(factor * fat),
+                sum(factor * fat_saturated),
+                sum(factor * carbs),
+                sum(factor * carbs_sugars),
+                sum(factor * fibre),
+                sum(factor * sodium)
+            from
+                scaled
+            group by
+                date
+            order by
+                date;
+        ";
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params![start, end], |row| {
+            Ok(DailyTotals {
+                date: row.get(0)?,
+                nutrition: Nutrition {
+                    energy: row.get(1)?,
+                    protein: row.get(2)?,
+                    fat: row.get(3)?,
+                    fat_saturated: row.get(4)?,
+                    carbs: row.get(5)?,
+                    carbs_sugars: row.get(6)?,
+                    fibre: row.get(7)?,
+                    sodium: row.get(8)?,
+                },
+            })
+        })?;
+        let mut totals = Vec::new();
+        for day in rows {
+            totals.push(day?);
+        }
+        Ok(totals)
+    }
+
+    /// Return the foods logged most recently, most recent first. Archived
+    /// foods are left out.
+    pub fn list_recent_foods(&self, limit: usize) -> Fallible<Vec<LoggedFood>> {
+        let sql = "
+            with latest as (
+                select
+                    food_id,
+                    serving_id,
+                    amount,
+                    row_number() over (
+                        partition by food_id
+                        order by date desc, eaten_at desc, created_at desc
+                    ) as position,
+                    date,
+                    eaten_at,
+                    created_at
+                from
+                    entries
+            )
+            select
+                f.food_id,
+                f.name,
+                f.brand,
+                f.serving_unit,
+                l.serving_id,
+                s.serving_name,
+                l.amount
+            from
+                latest l
+            join
+                foods f on f.food_id = l.food_id
+            left join
+                serving_sizes s on s.serving_id = l.serving_id
+            where
+                l.position = 1
+                and f.archived = 0
+            order by
+                l.date desc, l.eaten_at desc, l.created_at desc
+            limit ?1;
+        ";
+        self.query_logged_foods(sql, params![limit])
+    }
+
+    /// Return the foods logged most often in the `FREQUENT_WINDOW_DAYS` up
+    /// to `date`, most frequent first. Archived foods are left out.
+    ///
+    /// Each entry counts for less the older it is, and for more the closer it
+    /// was eaten to `time` of day, so that the foods usually eaten at this
+    /// time of day come first.
+    pub fn list_frequent_foods(
+        &self,
+        date: Date,
+        time: Time,
+        limit: usize,
+    ) -> Fallible<Vec<LoggedFood>> {
+        // An entry from `RECENCY_HALF_WEIGHT_DAYS` ago weighs half as much as
+        // one from today. An entry eaten at `time` weighs twice as much as
+        // one eaten `TIME_OF_DAY_WINDOW_MINUTES` or more away from it.
+        let sql = "
+            with latest as (
+                select
+                    food_id,
+                    serving_id,
+                    amount,
+                    row_number() over (
+                        partition by food_id
+                        order by date desc, eaten_at desc, created_at desc
+                    ) as position
+                from
+                    entries
+            ),
+            recent as (
+                select
+                    food_id,
+                    julianday(?1) - julianday(date) as age,
+                    cast(substr(eaten_at, 1, 2) as integer) * 60 + cast(substr(eaten_at, 4, 2) as integer) as minutes
+                from
+                    entries
+                where
+                    date <= ?1
+                    and julianday(?1) - julianday(date) < ?4
+            ),
+            scores as (
+                select
+                    food_id,
+                    sum(
+                        (1.0 / (1.0 + age / ?5))
+                        * (1.0 + max(0.0, 1.0 - min(abs(minutes - ?2), 1440 - abs(minutes - ?2)) / ?6))
+                    ) as score
+                from
+                    recent
+                group by
+                    food_id
+            )
+            select
+                f.food_id,
+                f.name,
+                f.brand,
+                f.serving_unit,
+                l.serving_id,
+                s.serving_name,
+                l.amount
+            from
+                scores sc
+            join
+                latest l on l.food_id = sc.food_id and l.position = 1
+            join
+                foods f on f.food_id = sc.food_id
+            left join
+                serving_sizes s on s.serving_id = l.serving_id
+            where
+                f.archived = 0
+            order by
+                sc.score desc, f.name
+            limit ?3;
+        ";
+        self.query_logged_foods(
+            sql,
+            params![
+                date,
+                time.minutes(),
+                limit,
+                FREQUENT_WINDOW_DAYS,
+                RECENCY_HALF_WEIGHT_DAYS,
+                TIME_OF_DAY_WINDOW_MINUTES
+            ],
+        )
+    }
+
+    fn query_logged_foods(&self, sql: &str, params: impl Params) -> Fallible<Vec<LoggedFood>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params, |row| {
+            let serving_unit_str: String = row.get(3)?;
+            let serving_unit = BasicUnit::try_from(serving_unit_str.as_str())
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+            Ok(LoggedFood {
+                food_id: row.get(0)?,
+                name: row.get(1)?,
+                brand: row.get(2)?,
+                serving_unit,
+                serving_id: row.get(4)?,
+                serving_name: row.get(5)?,
+                amount: row.get(6)?,
+            })
+        })?;
+        let mut foods = Vec::new();
+        for food in rows {
+            foods.push(food?);
+        }
+        Ok(foods)
+    }
+}
+
+/// How many days of entries are considered when ranking frequent foods.
+const FREQUENT_WINDOW_DAYS: f64 = 90.0;
+
+/// The age, in days, at which an entry counts half as much towards how
+/// frequent a food is.
+const RECENCY_HALF_WEIGHT_DAYS: f64 = 14.0;
+
+/// How close to the time of day, in minutes, an entry has to be eaten to count
+/// for more towards how frequent a food is.
+const TIME_OF_DAY_WINDOW_MINUTES: f64 = 180.0;
+
+/// The columns read by `read_entry`.
+const ENTRY_COLUMNS: &str = "
+    entry_id,
+    date,
+    food_id,
+    label,
+    serving_id,
+    amount,
+    eaten_at,
+    slot_id,
+    energy,
+    protein,
+    fat,
+    fat_saturated,
+    carbs,
+    carbs_sugars,
+    fibre,
+    sodium
+";
+
+fn read_entry(row: &Row) -> rusqlite::Result<Entry> {
+    Ok(Entry {
+        entry_id: row.get(0)?,
+        date: row.get(1)?,
+        food_id: row.get(2)?,
+        label: row.get(3)?,
+        serving_id: row.get(4)?,
+        amount: row.get(5)?,
+        eaten_at: row.get(6)?,
+        slot_id: row.get(7)?,
+        food_nutrition: Nutrition {
+            energy: row.get(8)?,
+            protein: row.get(9)?,
+            fat: row.get(10)?,
+            fat_saturated: row.get(11)?,
+            carbs: row.get(12)?,
+            carbs_sugars: row.get(13)?,
+            fibre: row.get(14)?,
+            sodium: row.get(15)?,
+        },
+    })
+}
+
+/// Read a target from the `nutrient, effective_from, min, max` columns.
+fn read_target(row: &Row) -> rusqlite::Result<Target> {
+    let nutrient_str: String = row.get(0)?;
+    let nutrient = Nutrient::try_from(nutrient_str.as_str())
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+    Ok(Target {
+        nutrient,
+        effective_from: row.get(1)?,
+        min: row.get(2)?,
+        max: row.get(3)?,
+    })
+}
+
+/// Turn user input into an FTS5 query that matches rows containing every word
+/// of the input as a prefix. Punctuation is dropped, so the input can't use
+/// (or break) the query syntax. Returns `None` if the input has no words.
+fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{word}\"*"))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
 }
@@ -15,22 +15,60 @@
 use std::error::Error;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::num::ParseFloatError;
 use std::num::ParseIntError;
-use std::sync::TryLockError;
+use std::os::raw::c_int;
 
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::response::Response;
+use rusqlite::ErrorCode;
+use rusqlite::ffi;
 
 #[derive(Debug)]
-pub struct AppError {
-    message: String,
+pub enum AppError {
+    /// The requested record doesn't exist.
+    NotFound(String),
+    /// The request is malformed or its values are invalid.
+    BadRequest(String),
+    /// The request conflicts with the current state of the database.
+    Conflict(String),
+    /// Anything else.
+    Internal(String),
 }
 
 impl AppError {
-    pub fn new(message: impl ToString) -> Self {
-        Self {
-            message: message.to_string(),
+    pub fn not_found(message: impl ToString) -> Self {
+        Self::NotFound(message.to_string())
+    }
+
+    pub fn bad_request(message: impl ToString) -> Self {
+        Self::BadRequest(message.to_string())
+    }
+
+    pub fn conflict(message: impl ToString) -> Self {
+        Self::Conflict(message.to_string())
+    }
+
+    pub fn internal(message: impl ToString) -> Self {
+        Self::Internal(message.to_string())
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound(message)
+            | Self::BadRequest(message)
+            | Self::Conflict(message)
+            | Self::Internal(message) => message,
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 }
@@ -39,51 +77,73 @@ pub type Fallible<T> = Result<T, AppError>;
 
 impl Display for AppError {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        write!(f, "Error: {}", self.message)
+        write!(f, "Error: {}", self.message())
     }
 }
 
-impl Error for AppError {
-    fn description(&self) -> &str {
-        &self.message
-    }
-}
+impl Error for AppError {}
 
 impl From<rusqlite::Error> for AppError {
     fn from(value: rusqlite::Error) -> Self {
-        AppError {
-            message: format!("rusqlite: {value}"),
+        match value {
+            rusqlite::Error::QueryReturnedNoRows => AppError::not_found("Not found."),
+            rusqlite::Error::SqliteFailure(e, ref message)
+                if e.code == ErrorCode::ConstraintViolation =>
+            {
+                // SQLite's messages name tables and columns, so only the raw
+                // error is logged, and the user gets a readable message.
+                eprintln!("rusqlite: {value}");
+                AppError::conflict(constraint_message(
+                    e.extended_code,
+                    message.as_deref().unwrap_or_default(),
+                ))
+            }
+            _ => AppError::internal(format!("rusqlite: {value}")),
         }
     }
 }
 
-impl From<std::io::Error> for AppError {
-    fn from(value: std::io::Error) -> Self {
-        AppError {
-            message: format!("I/O error: {value:#?}"),
+/// A readable message for a violation of one of the schema's constraints.
+fn constraint_message(extended_code: c_int, message: &str) -> &'static str {
+    match extended_code {
+        ffi::SQLITE_CONSTRAINT_UNIQUE => match message.strip_prefix("UNIQUE constraint failed: ") {
+            Some("serving_sizes.food_id, serving_sizes.serving_name") => {
+                "This food already has a serving with that name."
+            }
+            Some("meal_slots.name") => "A meal with that name already exists.",
+            Some("target_profiles.name") => "A target profile with that name already exists.",
+            Some("meal_templates.name") => "A meal template with that name already exists.",
+            _ => "This would duplicate an existing record.",
+        },
+        ffi::SQLITE_CONSTRAINT_FOREIGNKEY => {
+            "This is still in use elsewhere, or refers to something that doesn't exist."
         }
+        _ => "This change conflicts with the existing data.",
     }
 }
 
-impl<T> From<TryLockError<T>> for AppError {
-    fn from(_: TryLockError<T>) -> Self {
-        AppError {
-            message: "Failed to acquire lock on the database.".to_string(),
-        }
+impl From<std::io::Error> for AppError {
+    fn from(value: std::io::Error) -> Self {
+        AppError::internal(format!("I/O error: {value:#?}"))
     }
 }
 
 impl From<ParseIntError> for AppError {
     fn from(_: ParseIntError) -> Self {
-        AppError {
-            message: "failed to parse integer.".to_string(),
-        }
+        AppError::bad_request("failed to parse integer.")
+    }
+}
+
+impl From<ParseFloatError> for AppError {
+    fn from(_: ParseFloatError) -> Self {
+        AppError::bad_request("failed to parse number.")
     }
 }
 
 impl IntoResponse for AppError {
+    /// Errors are sent as plain text, which `www::render_errors` turns into an
+    /// HTML page or JSON, depending on what the client asked for.
     fn into_response(self) -> Response {
-        let msg = self.to_string();
-        (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
+        (self.status(), self.message().to_string()).into_response()
     }
 }
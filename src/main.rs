@@ -16,13 +16,16 @@ use std::process::ExitCode;
 
 use crate::cli::entrypoint;
 
+mod chart;
 mod cli;
 mod config;
 mod db;
 mod error;
+mod migrations;
 mod routes;
 mod types;
 mod ui;
+mod validation;
 mod www;
 
 #[tokio::main]
@@ -0,0 +1,34 @@
+-- A recipe is a food whose nutrition is derived from a list of ingredients.
+-- The nutrition columns of the recipe's row in `foods` are kept in sync with
+-- its ingredients, so a recipe can be logged like any other food.
+create table recipes (
+    -- The food this recipe defines.
+    food_id integer primary key,
+    -- Total weight of the cooked dish, in the food's `serving_unit`.
+    cooked_weight real not null,
+
+    foreign key (food_id) references foods(food_id) on delete cascade,
+    check(cooked_weight > 0)
+) strict;
+
+create table recipe_ingredients (
+    ingredient_id integer primary key,
+    -- The recipe this line belongs to.
+    recipe_id integer not null,
+    -- The food used as an ingredient. May itself be a recipe.
+    food_id integer not null,
+    -- If the user selected a serving: the ID of the serving. Otherwise,
+    -- the unit used is the base unit of the referenced food.
+    serving_id integer,
+    -- The amount of the ingredient.
+    amount real not null,
+
+    -- Timestamp when this record was created.
+    created_at text not null,
+
+    foreign key (recipe_id) references recipes(food_id) on delete cascade,
+    foreign key (food_id) references foods(food_id) on delete restrict,
+    foreign key (serving_id) references serving_sizes(serving_id) on delete restrict
+) strict;
+
+create index recipe_ingredients_food_id on recipe_ingredients(food_id);
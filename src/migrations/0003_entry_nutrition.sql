@@ -0,0 +1,22 @@
+-- Entries keep a copy of their food's nutrition (per 100 of the food's base
+-- unit) as it was when they were logged, so editing a food doesn't rewrite
+-- past totals.
+alter table entries add column energy real not null default 0;
+alter table entries add column protein real not null default 0;
+alter table entries add column fat real not null default 0;
+alter table entries add column fat_saturated real not null default 0;
+alter table entries add column carbs real not null default 0;
+alter table entries add column carbs_sugars real not null default 0;
+alter table entries add column fibre real not null default 0;
+alter table entries add column sodium real not null default 0;
+
+update entries
+set
+    (energy, protein, fat, fat_saturated, carbs, carbs_sugars, fibre, sodium) = (
+        select
+            energy, protein, fat, fat_saturated, carbs, carbs_sugars, fibre, sodium
+        from
+            foods
+        where
+            foods.food_id = entries.food_id
+    );
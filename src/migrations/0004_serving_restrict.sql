@@ -0,0 +1,56 @@
+-- Deleting a serving used to cascade into the entries that used it. Entries
+-- are now converted to base-unit amounts before a serving is deleted, and the
+-- foreign key refuses the deletion otherwise.
+create table entries_new (
+    entry_id integer primary key,
+
+    -- The date when the entry was created, in `YYYY-MM-DD` format.
+    date text not null,
+    -- The food that was logged.
+    food_id integer not null,
+    -- If the user selected a serving: the ID of the serving. Otherwise,
+    -- the unit used is the base unit of the referenced food.
+    serving_id integer,
+    -- The amount of food consumed.
+    amount real not null,
+
+    -- Timestamp when this record was created.
+    created_at text not null,
+
+    -- The nutrition of the food per 100 of its base unit, as it was when
+    -- the entry was logged. See `foods` for units.
+    energy real not null,
+    protein real not null,
+    fat real not null,
+    fat_saturated real not null,
+    carbs real not null,
+    carbs_sugars real not null,
+    fibre real not null,
+    sodium real not null,
+
+    foreign key (food_id) references foods(food_id) on delete cascade,
+    foreign key (serving_id) references serving_sizes(serving_id) on delete restrict
+);
+
+insert into entries_new
+select
+    entry_id,
+    date,
+    food_id,
+    serving_id,
+    amount,
+    created_at,
+    energy,
+    protein,
+    fat,
+    fat_saturated,
+    carbs,
+    carbs_sugars,
+    fibre,
+    sodium
+from
+    entries;
+
+drop table entries;
+
+alter table entries_new rename to entries;
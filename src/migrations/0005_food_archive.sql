@@ -0,0 +1,2 @@
+-- Archived foods are hidden from the food pickers, but keep their history.
+alter table foods add column archived integer not null default 0 check(archived in (0, 1));
@@ -0,0 +1,8 @@
+-- The time of day the food was eaten, in `HH:MM` format. Entries logged
+-- before this column existed are assumed to have been eaten when they were
+-- recorded.
+alter table entries add column eaten_at text not null default '00:00';
+
+update entries set eaten_at = strftime('%H:%M', created_at, 'localtime');
+
+create index entries_date_eaten_at on entries(date, eaten_at);
@@ -0,0 +1,41 @@
+-- Meals the entries of the daily log are grouped into.
+create table meal_slots (
+    slot_id integer primary key,
+    -- Name of this meal, e.g. "Breakfast".
+    name text not null unique,
+    -- Time of day (`HH:MM`) this meal starts. Food eaten between this time
+    -- and the start of the next meal defaults to this slot. `null` for slots
+    -- that are never picked by default, like snacks.
+    starts_at text,
+
+    -- Timestamp when this record was created.
+    created_at text not null
+) strict;
+
+insert into meal_slots (name, starts_at, created_at) values
+    ('Breakfast', '04:00', strftime('%Y-%m-%dT%H:%M:%fZ')),
+    ('Lunch', '11:00', strftime('%Y-%m-%dT%H:%M:%fZ')),
+    ('Dinner', '17:00', strftime('%Y-%m-%dT%H:%M:%fZ')),
+    ('Snacks', null, strftime('%Y-%m-%dT%H:%M:%fZ'));
+
+-- The meal this entry belongs to. `null` if it doesn't belong to any.
+alter table entries add column slot_id integer references meal_slots(slot_id) on delete set null;
+
+-- Put existing entries in the meal they would default to. Before the first
+-- meal of the day, that's the last meal of the previous day.
+update entries
+set
+    slot_id = coalesce(
+        (
+            select slot_id from meal_slots
+            where starts_at is not null and starts_at <= entries.eaten_at
+            order by starts_at desc
+            limit 1
+        ),
+        (
+            select slot_id from meal_slots
+            where starts_at is not null
+            order by starts_at desc
+            limit 1
+        )
+    );
@@ -0,0 +1,23 @@
+-- Daily nutrient targets. Targets are versioned by date: the target for a
+-- nutrient on a given day is the row with the latest `effective_from` on or
+-- before that day, so changing a target doesn't change how past days are
+-- judged.
+create table targets (
+    target_id integer primary key,
+    -- The nutrient, named after its column in `foods`.
+    nutrient text not null,
+    -- The date from which this target applies, in `YYYY-MM-DD` format.
+    effective_from text not null,
+    -- Minimum daily amount, in the units of `foods`. `null` for no minimum.
+    min real,
+    -- Maximum daily amount, in the units of `foods`. `null` for no maximum.
+    -- A row with neither a minimum nor a maximum removes the target.
+    max real,
+
+    -- Timestamp when this record was created.
+    created_at text not null,
+
+    unique(nutrient, effective_from),
+    check(nutrient in ('energy', 'protein', 'fat', 'fat_saturated', 'carbs', 'carbs_sugars', 'fibre', 'sodium')),
+    check(min is null or max is null or min <= max)
+) strict;
@@ -0,0 +1,62 @@
+-- Named sets of targets, e.g. for training days and rest days.
+create table target_profiles (
+    profile_id integer primary key,
+    -- Name of this profile, e.g. "Training Day".
+    name text not null unique,
+
+    -- Timestamp when this record was created.
+    created_at text not null
+) strict;
+
+-- The default profile, which applies to days without a rule of their own.
+-- Existing targets belong to it.
+insert into target_profiles (profile_id, name, created_at) values
+    (1, 'Default', strftime('%Y-%m-%dT%H:%M:%fZ'));
+
+-- Rebuild `targets` so targets belong to a profile.
+create table targets_new (
+    target_id integer primary key,
+    -- The profile this target belongs to.
+    profile_id integer not null references target_profiles(profile_id) on delete cascade,
+    -- The nutrient, named after its column in `foods`.
+    nutrient text not null,
+    -- The date from which this target applies, in `YYYY-MM-DD` format.
+    effective_from text not null,
+    -- Minimum daily amount, in the units of `foods`. `null` for no minimum.
+    min real,
+    -- Maximum daily amount, in the units of `foods`. `null` for no maximum.
+    -- A row with neither a minimum nor a maximum removes the target.
+    max real,
+
+    -- Timestamp when this record was created.
+    created_at text not null,
+
+    unique(profile_id, nutrient, effective_from),
+    check(nutrient in ('energy', 'protein', 'fat', 'fat_saturated', 'carbs', 'carbs_sugars', 'fibre', 'sodium')),
+    check(min is null or max is null or min <= max)
+) strict;
+
+insert into targets_new (target_id, profile_id, nutrient, effective_from, min, max, created_at)
+select target_id, 1, nutrient, effective_from, min, max, created_at from targets;
+
+drop table targets;
+
+alter table targets_new rename to targets;
+
+-- The profile that applies on each day of the week, unless overridden.
+create table profile_weekdays (
+    -- Day of the week, from 0 (Monday) to 6 (Sunday).
+    weekday integer primary key,
+    -- The profile that applies on this day.
+    profile_id integer not null references target_profiles(profile_id) on delete cascade,
+
+    check(weekday between 0 and 6)
+) strict;
+
+-- Profiles chosen for specific days, overriding the weekday rules.
+create table profile_overrides (
+    -- The date, in `YYYY-MM-DD` format.
+    date text primary key,
+    -- The profile that applies on this date.
+    profile_id integer not null references target_profiles(profile_id) on delete cascade
+) strict;
@@ -0,0 +1,25 @@
+-- Full-text index over the name and brand of each food. The index doesn't
+-- store the text itself, it reads it from `foods`, and the triggers below keep
+-- the two in sync.
+create virtual table foods_fts using fts5(
+    name,
+    brand,
+    content = 'foods',
+    content_rowid = 'food_id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+insert into foods_fts (foods_fts) values ('rebuild');
+
+create trigger foods_fts_insert after insert on foods begin
+    insert into foods_fts (rowid, name, brand) values (new.food_id, new.name, new.brand);
+end;
+
+create trigger foods_fts_delete after delete on foods begin
+    insert into foods_fts (foods_fts, rowid, name, brand) values ('delete', old.food_id, old.name, old.brand);
+end;
+
+create trigger foods_fts_update after update of name, brand on foods begin
+    insert into foods_fts (foods_fts, rowid, name, brand) values ('delete', old.food_id, old.name, old.brand);
+    insert into foods_fts (rowid, name, brand) values (new.food_id, new.name, new.brand);
+end;
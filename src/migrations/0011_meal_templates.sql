@@ -0,0 +1,34 @@
+-- Named lists of foods that are often eaten together, e.g. a usual breakfast,
+-- which can be logged in one go.
+create table meal_templates (
+    template_id integer primary key,
+    -- Name of this template, e.g. "Weekday Breakfast".
+    name text not null unique,
+
+    -- Timestamp when this record was created.
+    created_at text not null
+) strict;
+
+create table meal_template_lines (
+    line_id integer primary key,
+    -- The template this line belongs to.
+    template_id integer not null,
+    -- The food to log.
+    food_id integer not null,
+    -- If the user selected a serving: the ID of the serving. Otherwise,
+    -- the unit used is the base unit of the referenced food.
+    serving_id integer,
+    -- The amount of the food.
+    amount real not null,
+
+    -- Timestamp when this record was created.
+    created_at text not null,
+
+    foreign key (template_id) references meal_templates(template_id) on delete cascade,
+    foreign key (food_id) references foods(food_id) on delete restrict,
+    foreign key (serving_id) references serving_sizes(serving_id) on delete restrict,
+    check(amount > 0)
+) strict;
+
+create index meal_template_lines_template_id on meal_template_lines(template_id);
+create index meal_template_lines_food_id on meal_template_lines(food_id);
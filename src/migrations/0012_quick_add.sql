@@ -0,0 +1,74 @@
+-- Quick-add entries record an estimate without a food from the library, e.g.
+-- for a meal at a restaurant. Rebuild `entries` so `food_id` is nullable and
+-- entries can carry a label instead.
+create table entries_new (
+    entry_id integer primary key,
+
+    -- The date when the entry was created, in `YYYY-MM-DD` format.
+    date text not null,
+    -- The food that was logged. `null` for quick-add entries.
+    food_id integer,
+    -- What a quick-add entry was, e.g. "Burger at the pub". `null` for
+    -- entries of a food.
+    label text,
+    -- If the user selected a serving: the ID of the serving. Otherwise,
+    -- the unit used is the base unit of the referenced food.
+    serving_id integer,
+    -- The amount of food consumed. Always 100 for quick-add entries, see
+    -- below.
+    amount real not null,
+    -- The time of day the food was eaten, in `HH:MM` format.
+    eaten_at text not null,
+    -- The meal this entry belongs to. `null` if it doesn't belong to any.
+    slot_id integer,
+
+    -- Timestamp when this record was created.
+    created_at text not null,
+
+    -- The nutrition of the food per 100 of its base unit, as it was when
+    -- the entry was logged. See `foods` for units. For quick-add entries,
+    -- the nutrition of the whole entry: with an amount of 100, the nutrition
+    -- of every entry is `amount * value / 100`.
+    energy real not null,
+    protein real not null,
+    fat real not null,
+    fat_saturated real not null,
+    carbs real not null,
+    carbs_sugars real not null,
+    fibre real not null,
+    sodium real not null,
+
+    foreign key (food_id) references foods(food_id) on delete cascade,
+    foreign key (serving_id) references serving_sizes(serving_id) on delete restrict,
+    foreign key (slot_id) references meal_slots(slot_id) on delete set null,
+    check((food_id is null) = (label is not null)),
+    check(food_id is not null or serving_id is null)
+);
+
+insert into entries_new
+select
+    entry_id,
+    date,
+    food_id,
+    null,
+    serving_id,
+    amount,
+    eaten_at,
+    slot_id,
+    created_at,
+    energy,
+    protein,
+    fat,
+    fat_saturated,
+    carbs,
+    carbs_sugars,
+    fibre,
+    sodium
+from
+    entries;
+
+drop table entries;
+
+alter table entries_new rename to entries;
+
+create index entries_date_eaten_at on entries(date, eaten_at);
@@ -0,0 +1,15 @@
+-- Quick-add entries store the nutrition of the whole entry, and are read as
+-- `amount * value / 100` like every other entry, so their amount must stay at
+-- 100 (`QUICK_ADD_AMOUNT`). An entry that becomes an entry of a food (i.e. is
+-- converted) gets a food, so it's free to change its amount.
+create trigger entries_quick_add_amount_insert before insert on entries
+when new.food_id is null and new.amount != 100
+begin
+    select raise(abort, 'quick-add entries must have an amount of 100');
+end;
+
+create trigger entries_quick_add_amount_update before update of food_id, amount on entries
+when new.food_id is null and new.amount != 100
+begin
+    select raise(abort, 'quick-add entries must have an amount of 100');
+end;
@@ -0,0 +1,173 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use rusqlite::Connection;
+use rusqlite::config::DbConfig;
+
+use crate::error::AppError;
+use crate::error::Fallible;
+
+/// A single step in the evolution of the database schema.
+pub struct Migration {
+    /// The schema version the database is at after this migration is applied.
+    pub version: i64,
+    /// Short description of the migration.
+    pub name: &'static str,
+    /// The SQL to execute.
+    sql: &'static str,
+}
+
+/// All migrations, in the order they are applied. Versions start at 1 and are
+/// contiguous. Migrations that have been released must never be edited: add a
+/// new one instead.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial schema",
+        sql: include_str!("0001_initial.sql"),
+    },
+    Migration {
+        version: 2,
+        name: "recipes",
+        sql: include_str!("0002_recipes.sql"),
+    },
+    Migration {
+        version: 3,
+        name: "entry nutrition snapshots",
+        sql: include_str!("0003_entry_nutrition.sql"),
+    },
+    Migration {
+        version: 4,
+        name: "restrict serving deletion",
+        sql: include_str!("0004_serving_restrict.sql"),
+    },
+    Migration {
+        version: 5,
+        name: "food archiving",
+        sql: include_str!("0005_food_archive.sql"),
+    },
+    Migration {
+        version: 6,
+        name: "entry eaten-at time",
+        sql: include_str!("0006_eaten_at.sql"),
+    },
+    Migration {
+        version: 7,
+        name: "meal slots",
+        sql: include_str!("0007_meal_slots.sql"),
+    },
+    Migration {
+        version: 8,
+        name: "nutrient targets",
+        sql: include_str!("0008_targets.sql"),
+    },
+    Migration {
+        version: 9,
+        name: "target profiles",
+        sql: include_str!("0009_target_profiles.sql"),
+    },
+    Migration {
+        version: 10,
+        name: "food search",
+        sql: include_str!("0010_food_search.sql"),
+    },
+    Migration {
+        version: 11,
+        name: "meal templates",
+        sql: include_str!("0011_meal_templates.sql"),
+    },
+    Migration {
+        version: 12,
+        name: "quick-add entries",
+        sql: include_str!("0012_quick_add.sql"),
+    },
+    Migration {
+        version: 13,
+        name: "quick-add entry amount",
+        sql: include_str!("0013_quick_add_amount.sql"),
+    },
+];
+
+/// The schema version this binary expects.
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Return the schema version of the database, as tracked by `user_version`.
+pub fn current_version(conn: &Connection) -> Fallible<i64> {
+    let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version == 0 && has_legacy_schema(conn)? {
+        // Databases created before migrations existed have the initial schema
+        // but were never stamped with a version.
+        return Ok(1);
+    }
+    Ok(version)
+}
+
+/// Return the migrations that have not been applied to the database yet.
+///
+/// Fails if the database is newer than this binary.
+pub fn pending(conn: &Connection) -> Fallible<Vec<&'static Migration>> {
+    let version = current_version(conn)?;
+    let latest = latest_version();
+    if version > latest {
+        return Err(AppError::internal(format!(
+            "database schema version {version} is newer than the latest version supported by this binary ({latest})"
+        )));
+    }
+    Ok(MIGRATIONS.iter().filter(|m| m.version > version).collect())
+}
+
+/// Apply all pending migrations in a single transaction, and return them.
+pub fn migrate(conn: &mut Connection) -> Fallible<Vec<&'static Migration>> {
+    let pending = pending(conn)?;
+    if pending.is_empty() {
+        return Ok(pending);
+    }
+    // Foreign key enforcement is turned off while migrating so that tables can
+    // be rebuilt. It can't be toggled inside a transaction, hence out here.
+    conn.set_db_config(DbConfig::SQLITE_DBCONFIG_ENABLE_FKEY, false)?;
+    let result = apply(conn, &pending);
+    conn.set_db_config(DbConfig::SQLITE_DBCONFIG_ENABLE_FKEY, true)?;
+    result?;
+    Ok(pending)
+}
+
+fn apply(conn: &mut Connection, migrations: &[&Migration]) -> Fallible<()> {
+    let tx = conn.transaction()?;
+    for migration in migrations {
+        tx.execute_batch(migration.sql)?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+    }
+    // Since enforcement was off, check the migrations left no dangling
+    // references behind. Dropping the transaction rolls it back.
+    let violations: i64 = tx.query_row(
+        "select count(*) from pragma_foreign_key_check;",
+        [],
+        |row| row.get(0),
+    )?;
+    if violations > 0 {
+        return Err(AppError::internal(format!(
+            "migration would leave {violations} foreign key violations"
+        )));
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+fn has_legacy_schema(conn: &Connection) -> Fallible<bool> {
+    let sql = "select exists(select 1 from sqlite_master where type = 'table' and name = 'foods');";
+    let exists: bool = conn.query_row(sql, [], |row| row.get(0))?;
+    Ok(exists)
+}
@@ -0,0 +1,205 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::routing::get;
+use maud::Markup;
+use maud::html;
+
+use crate::db::DailyTotals;
+use crate::db::Target;
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::routes::log_view::LogViewHandler;
+use crate::routes::week_view::WeekViewHandler;
+use crate::types::Date;
+use crate::types::EnergyUnit;
+use crate::types::Nutrient;
+use crate::ui::*;
+use crate::www::ServerState;
+
+/// Shows a month as a grid, with each day coloured by its energy total.
+pub struct CalendarViewHandler {}
+
+impl CalendarViewHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route("/calendar/{year}/{month}", get(handler))
+    }
+
+    /// The calendar of the month the date falls in.
+    pub fn url(date: Date) -> String {
+        format!("/calendar/{}/{}", date.year(), date.month())
+    }
+}
+
+/// A logged day's energy total, and the energy target that applied.
+struct DayEnergy {
+    energy: f64,
+    target: Option<Target>,
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Path((year, month)): Path<(i32, u32)>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    let first: Date = Date::from_ymd(year, month, 1)
+        .ok_or_else(|| AppError::bad_request(format!("invalid month: {year}-{month}")))?;
+    let last: Date = first.next_month().prev_day();
+    let energy_unit: EnergyUnit = state.energy_unit;
+    state
+        .db
+        .run(move |db| {
+            let totals: Vec<DailyTotals> = db.list_daily_totals(first, last)?;
+            let mut targets: HashMap<Date, Vec<Target>> = db.list_day_targets(first, last)?;
+            let mut days: HashMap<Date, DayEnergy> = HashMap::new();
+            for day in totals {
+                let target = targets
+                    .remove(&day.date)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .find(|target| target.nutrient == Nutrient::Energy);
+                days.insert(
+                    day.date,
+                    DayEnergy {
+                        energy: day.nutrition.energy,
+                        target,
+                    },
+                );
+            }
+            let content = html! {
+                .button-bar {
+                    a .button href=(CalendarViewHandler::url(first.prev_month())) {
+                        "← Previous"
+                    }
+                    a .button href=(CalendarViewHandler::url(Date::today())) {
+                        "This Month"
+                    }
+                    a .button href=(CalendarViewHandler::url(first.next_month())) {
+                        "Next →"
+                    }
+                }
+                (render_calendar(first, last, &days, energy_unit))
+                (render_legend())
+            };
+            let title = first.humanize_month();
+            let html_page = page(&title, content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+fn render_calendar(
+    first: Date,
+    last: Date,
+    days: &HashMap<Date, DayEnergy>,
+    energy_unit: EnergyUnit,
+) -> Markup {
+    let today = Date::today();
+    // Whole weeks, so the grid lines up by weekday.
+    let mut weeks: Vec<Date> = Vec::new();
+    let mut week = first.week_start();
+    while week <= last {
+        weeks.push(week);
+        week = week.add_days(7);
+    }
+    html! {
+        table .calendar {
+            thead {
+                tr {
+                    th .week-link {}
+                    @for name in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"] {
+                        th { (name) }
+                    }
+                }
+            }
+            tbody {
+                @for week in weeks {
+                    tr {
+                        th .week-link {
+                            a href=(WeekViewHandler::url(week)) title="View week" { "Week" }
+                        }
+                        @for offset in 0..7 {
+                            @let date = week.add_days(offset);
+                            @if date < first || date > last {
+                                td .outside {}
+                            } @else {
+                                (render_day(date, days.get(&date), date == today, energy_unit))
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn render_day(
+    date: Date,
+    day: Option<&DayEnergy>,
+    is_today: bool,
+    energy_unit: EnergyUnit,
+) -> Markup {
+    let (class, title) = match day {
+        None => ("unlogged".to_string(), "Nothing logged".to_string()),
+        Some(day) => {
+            let energy = format!(
+                "{} {}",
+                humanize_float(Nutrient::Energy.display_value(day.energy, energy_unit)),
+                Nutrient::Energy.display_unit(energy_unit)
+            );
+            match &day.target {
+                Some(target) => (
+                    target.status(day.energy).as_str().to_string(),
+                    format!("{energy} (target {})", target.describe(energy_unit)),
+                ),
+                None => ("logged".to_string(), energy),
+            }
+        }
+    };
+    let class = if is_today {
+        format!("{class} today")
+    } else {
+        class
+    };
+    html! {
+        td class=(class) title=(title) {
+            a href=(LogViewHandler::url(date)) {
+                span .day-number { (date.day()) }
+                @if let Some(day) = day {
+                    span .day-energy {
+                        (format!("{:.0}", Nutrient::Energy.display_value(day.energy, energy_unit)))
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn render_legend() -> Markup {
+    html! {
+        ul .calendar-legend {
+            li { span .swatch .within {} "Within target" }
+            li { span .swatch .under {} "Under target" }
+            li { span .swatch .over {} "Over target" }
+            li { span .swatch .logged {} "Logged, no target" }
+            li { span .swatch .unlogged {} "Nothing logged" }
+        }
+    }
+}
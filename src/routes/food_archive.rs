@@ -0,0 +1,57 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::response::Redirect;
+use axum::routing::post;
+use serde::Deserialize;
+
+use crate::db::FoodId;
+use crate::error::Fallible;
+use crate::routes::food_view::FoodViewHandler;
+use crate::www::ServerState;
+
+pub struct FoodArchiveHandler {}
+
+impl FoodArchiveHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route("/library/{food_id}/archive", post(handler))
+    }
+
+    pub fn url(food_id: FoodId) -> String {
+        format!("/library/{food_id}/archive")
+    }
+}
+
+#[derive(Deserialize)]
+struct ArchiveFoodForm {
+    archived: bool,
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Path(food_id): Path<FoodId>,
+    Form(form): Form<ArchiveFoodForm>,
+) -> Fallible<Redirect> {
+    state
+        .db
+        .run(move |db| {
+            db.set_food_archived(food_id, form.archived)?;
+            Ok(Redirect::to(&FoodViewHandler::url(food_id)))
+        })
+        .await
+}
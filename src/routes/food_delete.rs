@@ -0,0 +1,123 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::response::Redirect;
+use axum::routing::get;
+use axum::routing::post;
+use maud::html;
+use serde::Deserialize;
+
+use crate::db::FoodEntry;
+use crate::db::FoodId;
+use crate::db::FoodUsage;
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::routes::food_list::FoodListHandler;
+use crate::routes::food_view::FoodViewHandler;
+use crate::ui::*;
+use crate::www::ServerState;
+
+pub struct FoodDeleteHandler {}
+
+impl FoodDeleteHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route("/library/{food_id}/delete", get(get_handler));
+        router.route("/library/{food_id}/delete", post(post_handler))
+    }
+
+    pub fn url(food_id: FoodId) -> String {
+        format!("/library/{food_id}/delete")
+    }
+}
+
+async fn get_handler(
+    State(state): State<ServerState>,
+    Path(food_id): Path<FoodId>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    state
+        .db
+        .run(move |db| {
+            let food: FoodEntry = db.get_food(food_id)?;
+            let usage: FoodUsage = db.get_food_usage(food_id)?;
+
+            let content = if usage.recipes > 0 {
+                html! {
+                    p {
+                        (format!("This food can't be deleted, because {} recipes use it as an ingredient. Remove it from those recipes first, or archive it instead.", usage.recipes))
+                    }
+                    .button-bar {
+                        a .button href=(FoodViewHandler::url(food_id)) {
+                            "Back"
+                        }
+                    }
+                }
+            } else {
+                html! {
+                    @if usage.entries == 0 {
+                        p {
+                            "No log entries use this food."
+                        }
+                    } @else {
+                        p {
+                            (format!("{} log entries use this food. Deleting it will delete them too, changing the totals of the days they were logged on. To hide the food while keeping its history, archive it instead.", usage.entries))
+                        }
+                    }
+                    @if usage.templates > 0 {
+                        p {
+                            (format!("It will also be removed from {} meal templates.", usage.templates))
+                        }
+                    }
+                    (confirm_form(&FoodDeleteHandler::url(food_id), &[], "Delete Food", &FoodViewHandler::url(food_id)))
+                }
+            };
+
+            let html_page = page(&format!("Delete {}", food.name), content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+#[derive(Deserialize)]
+struct DeleteFoodForm {
+    /// Set by the confirmation page. Required if the food has log entries,
+    /// since deleting it deletes them too.
+    #[serde(default)]
+    confirmed: bool,
+}
+
+async fn post_handler(
+    State(state): State<ServerState>,
+    Path(food_id): Path<FoodId>,
+    Form(form): Form<DeleteFoodForm>,
+) -> Fallible<Redirect> {
+    state
+        .db
+        .run(move |db| {
+            let usage: FoodUsage = db.get_food_usage(food_id)?;
+            if usage.entries > 0 && !form.confirmed {
+                return Err(AppError::bad_request(
+                    "Deleting this food deletes its log entries. Confirm the deletion first.",
+                ));
+            }
+            db.delete_food(food_id)?;
+            Ok(Redirect::to(FoodListHandler::url()))
+        })
+        .await
+}
@@ -18,21 +18,35 @@ use axum::extract::Path;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::Html;
+use axum::response::IntoResponse;
 use axum::response::Redirect;
+use axum::response::Response;
 use axum::routing::get;
 use axum::routing::post;
+use chrono::Utc;
 use maud::html;
-use serde::Deserialize;
 
 use crate::db::EditFoodInput;
 use crate::db::FoodEntry;
 use crate::db::FoodId;
+use crate::db::NewServing;
 use crate::error::Fallible;
+use crate::routes::food_form::FoodForm;
+use crate::routes::food_form::ParsedFood;
 use crate::routes::food_view::FoodViewHandler;
-use crate::types::BasicUnit;
+use crate::routes::recipe_edit::RecipeEditHandler;
+use crate::types::Date;
+use crate::types::EnergyUnit;
+use crate::types::LabelBasis;
+use crate::types::Nutrition;
 use crate::ui::*;
+use crate::validation::FieldErrors;
 use crate::www::ServerState;
 
+/// The largest relative difference between a submitted and a stored value
+/// that is put down to floating-point rounding.
+const ROUNDING_ERROR: f64 = 1e-12;
+
 pub struct FoodEditHandler {}
 
 impl FoodEditHandler {
@@ -49,46 +63,106 @@ impl FoodEditHandler {
 async fn get_handler(
     State(state): State<ServerState>,
     Path(food_id): Path<FoodId>,
-) -> Fallible<(StatusCode, Html<String>)> {
-    let db = state.db.try_lock()?;
-    let food: FoodEntry = db.get_food(food_id)?;
+) -> Fallible<Response> {
+    let energy_unit = state.energy_unit;
+    state
+        .db
+        .run(move |db| {
+            let food: FoodEntry = db.get_food(food_id)?;
+            // The nutrition of a recipe is derived, so it has its own form.
+            if db.get_recipe(food_id)?.is_some() {
+                return Ok(Redirect::to(&RecipeEditHandler::url(food_id)).into_response());
+            }
 
+            let form = FoodForm {
+                food_name: food.name.clone(),
+                brand: food.brand.clone(),
+                serving_unit: food.serving_unit.as_str().to_string(),
+                // Full precision, so saving without touching a field doesn't
+                // round its stored value.
+                energy: energy_unit.convert_kcal(food.energy).to_string(),
+                energy_unit: energy_unit.as_str().to_string(),
+                protein: food.protein.to_string(),
+                fat: food.fat.to_string(),
+                fat_saturated: food.fat_saturated.to_string(),
+                carbs: food.carbs.to_string(),
+                carbs_sugars: food.carbs_sugars.to_string(),
+                fibre: food.fibre.to_string(),
+                sodium: food.sodium.to_string(),
+                basis: LabelBasis::Per100.as_str().to_string(),
+                ..FoodForm::default()
+            };
+            Ok((
+                StatusCode::OK,
+                render_form(&food, &form, &FieldErrors::new(), energy_unit),
+            )
+                .into_response())
+        })
+        .await
+}
+
+/// Render the form, filled in with the given values and errors.
+fn render_form(
+    food: &FoodEntry,
+    form: &FoodForm,
+    errors: &FieldErrors,
+    energy_unit: EnergyUnit,
+) -> Html<String> {
+    let energy_unit = EnergyUnit::try_from(form.energy_unit.as_str()).unwrap_or(energy_unit);
     let form_content = html! {
-        form method="post" action=(FoodEditHandler::url(food_id)) {
+        form method="post" action=(FoodEditHandler::url(food.food_id)) {
             // Basic Information Section
             (form_section("Basic Information", html! {
                 (form_row(html! {
                     (form_group(html! {
                         (label_required("food_name", "Food Name"))
-                        (text_input_value("food_name", "food_name", &food.name, "e.g., Rolled Oats"))
+                        (text_input_value("food_name", "food_name", &form.food_name, "e.g., Rolled Oats"))
+                        (field_error(errors, "food_name"))
                     }))
                 }))
                 (form_row(html! {
                     (form_group_half(html! {
                         (label_with_hint("brand", "Brand", "(optional, leave blank for generic foods)"))
-                        (text_input_value("brand", "brand", &food.brand, "e.g., Uncle Tobys"))
+                        (text_input_value("brand", "brand", &form.brand, "e.g., Uncle Tobys"))
                     }))
                     (form_group_half(html! {
                         (label_required("serving_unit", "Base Unit"))
                         (select_with_selected("serving_unit", "serving_unit", vec![
                             ("g".to_string(), "Grams (g)".to_string()),
                             ("ml".to_string(), "Milliliters (ml)".to_string()),
-                        ], food.serving_unit.as_str()))
+                        ], &form.serving_unit))
                     }))
                 }))
             }))
 
+            // Label Section
+            (label_basis_section(&form.basis, &form.serving_size, &form.serving_name, errors))
+
             // Nutrition Information Section
-            (form_section("Nutrition Information (per 100g or 100ml)", html! {
+            (form_section("Nutrition Information", html! {
+                (field_error(errors, "nutrition"))
                 (nutrition_table(html! {
-                    (nutrition_row_with_value("Energy *", "energy", "energy", "kcal", &format!("{:.1}", food.energy), 0))
-                    (nutrition_row_with_value("Protein *", "protein", "protein", "g", &format!("{:.1}", food.protein), 0))
-                    (nutrition_row_with_value("Fat, Total *", "fat", "fat", "g", &format!("{:.1}", food.fat), 0))
-                    (nutrition_row_with_value("Saturated *", "fat_saturated", "fat_saturated", "g", &format!("{:.1}", food.fat_saturated), 1))
-                    (nutrition_row_with_value("Carbohydrate *", "carbs", "carbs", "g", &format!("{:.1}", food.carbs), 0))
-                    (nutrition_row_with_value("Sugars *", "carbs_sugars", "carbs_sugars", "g", &format!("{:.1}", food.carbs_sugars), 1))
-                    (nutrition_row_with_value("Dietary Fibre *", "fibre", "fibre", "g", &format!("{:.1}", food.fibre), 0))
-                    (nutrition_row_with_value("Sodium *", "sodium", "sodium", "mg", &format!("{:.0}", food.sodium), 0))
+                    (energy_row("Energy *", &form.energy, energy_unit))
+                    (field_error(errors, "energy"))
+                    (form.energy_confirmation(errors))
+                    (nutrition_row_checked("Protein *", "protein", "g", &form.protein, 0, errors))
+                    (nutrition_row_checked("Fat, Total *", "fat", "g", &form.fat, 0, errors))
+                    (nutrition_row_checked("Saturated *", "fat_saturated", "g", &form.fat_saturated, 1, errors))
+                    (nutrition_row_checked("Carbohydrate *", "carbs", "g", &form.carbs, 0, errors))
+                    (nutrition_row_checked("Sugars *", "carbs_sugars", "g", &form.carbs_sugars, 1, errors))
+                    (nutrition_row_checked("Dietary Fibre *", "fibre", "g", &form.fibre, 0, errors))
+                    (nutrition_row_checked("Sodium *", "sodium", "mg", &form.sodium, 0, errors))
+                }))
+            }))
+
+            // Past Entries Section
+            (form_section("Past Entries", html! {
+                (form_row(html! {
+                    (form_group_half(html! {
+                        (label_with_hint("correct_from", "Apply Correction From", "(optional, leave blank to leave past entries unchanged)"))
+                        input type="date" id="correct_from" name="correct_from" value=(form.correct_from);
+                        (field_error(errors, "correct_from"))
+                    }))
                 }))
             }))
 
@@ -100,57 +174,67 @@ async fn get_handler(
     };
 
     let html_page = page(&format!("Edit {}", food.name), form_content);
-    Ok((StatusCode::OK, Html(html_page.into_string())))
-}
-
-#[derive(Deserialize)]
-struct EditFoodForm {
-    food_name: String,
-    brand: String,
-    serving_unit: String,
-    energy: f64,
-    protein: f64,
-    fat: f64,
-    fat_saturated: f64,
-    carbs: f64,
-    carbs_sugars: f64,
-    fibre: f64,
-    sodium: f64,
+    Html(html_page.into_string())
 }
 
 async fn post_handler(
     State(state): State<ServerState>,
     Path(food_id): Path<FoodId>,
-    Form(form): Form<EditFoodForm>,
-) -> Fallible<Redirect> {
-    let EditFoodForm {
-        food_name,
-        brand,
-        serving_unit,
-        energy,
-        protein,
-        fat,
-        fat_saturated,
-        carbs,
-        carbs_sugars,
-        fibre,
-        sodium,
-    } = form;
-    let input = EditFoodInput {
+    Form(form): Form<FoodForm>,
+) -> Fallible<Response> {
+    let mut errors = FieldErrors::new();
+    let food: ParsedFood = form.parse_and_validate(&mut errors)?;
+    let correct_from: Option<Date> = if form.correct_from.is_empty() {
+        None
+    } else {
+        match Date::try_from(form.correct_from.clone()) {
+            Ok(date) => Some(date),
+            Err(_) => {
+                errors.add("correct_from", "Must be a date.");
+                None
+            }
+        }
+    };
+    if !errors.is_empty() {
+        let energy_unit = state.energy_unit;
+        return state
+            .db
+            .run(move |db| {
+                let food: FoodEntry = db.get_food(food_id)?;
+                let html = render_form(&food, &form, &errors, energy_unit);
+                Ok((StatusCode::UNPROCESSABLE_ENTITY, html).into_response())
+            })
+            .await;
+    }
+    let serving: Option<NewServing> = form.new_serving(&food, Utc::now());
+    let nutrition: Nutrition = food.nutrition;
+    let mut input = EditFoodInput {
         food_id,
-        name: food_name,
-        brand,
-        serving_unit: BasicUnit::try_from(serving_unit.as_ref())?,
-        energy,
-        protein,
-        fat,
-        fat_saturated,
-        carbs,
-        carbs_sugars,
-        fibre,
-        sodium,
+        name: form.food_name,
+        brand: form.brand,
+        serving_unit: food.serving_unit,
+        energy: nutrition.energy,
+        protein: nutrition.protein,
+        fat: nutrition.fat,
+        fat_saturated: nutrition.fat_saturated,
+        carbs: nutrition.carbs,
+        carbs_sugars: nutrition.carbs_sugars,
+        fibre: nutrition.fibre,
+        sodium: nutrition.sodium,
+        correct_from,
+        serving,
     };
-    let db = state.db.try_lock()?;
-    db.edit_food(input)?;
-    Ok(Redirect::to(&FoodViewHandler::url(food_id)))
+    state
+        .db
+        .run(move |db| {
+            // Converting the stored energy to kJ for the form and back can
+            // change its last digit. Keep the stored value in that case.
+            let stored: f64 = db.get_food(food_id)?.energy;
+            if (input.energy - stored).abs() <= stored * ROUNDING_ERROR {
+                input.energy = stored;
+            }
+            db.edit_food(input)?;
+            Ok(Redirect::to(&FoodViewHandler::url(food_id)).into_response())
+        })
+        .await
 }
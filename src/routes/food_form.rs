@@ -0,0 +1,159 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use chrono::DateTime;
+use chrono::Utc;
+use maud::Markup;
+use maud::html;
+use serde::Deserialize;
+
+use crate::db::NewServing;
+use crate::error::Fallible;
+use crate::types::BasicUnit;
+use crate::types::EnergyUnit;
+use crate::types::LabelBasis;
+use crate::types::Nutrition;
+use crate::ui::*;
+use crate::validation::FieldErrors;
+
+/// The values submitted by the forms for creating and editing a food.
+#[derive(Deserialize, Default)]
+pub struct FoodForm {
+    pub food_name: String,
+    pub brand: String,
+    pub serving_unit: String,
+    pub energy: String,
+    pub energy_unit: String,
+    pub protein: String,
+    pub fat: String,
+    pub fat_saturated: String,
+    pub carbs: String,
+    pub carbs_sugars: String,
+    pub fibre: String,
+    pub sodium: String,
+    pub basis: String,
+    pub serving_size: String,
+    pub serving_name: String,
+    /// Only on the edit form.
+    #[serde(default)]
+    pub correct_from: String,
+    /// Checked once the user has confirmed a suspicious energy value.
+    #[serde(default)]
+    pub confirmed: bool,
+}
+
+/// A food form, parsed and normalised to kcal per 100 of the base unit.
+pub struct ParsedFood {
+    pub serving_unit: BasicUnit,
+    pub serving_size: Option<f64>,
+    pub nutrition: Nutrition,
+}
+
+impl FoodForm {
+    /// Parse and validate the form, recording errors in `errors`. The
+    /// nutrition is only checked for physical plausibility once every field
+    /// has parsed.
+    pub fn parse_and_validate(&self, errors: &mut FieldErrors) -> Fallible<ParsedFood> {
+        errors.required("food_name", &self.food_name);
+        let serving_unit = BasicUnit::try_from(self.serving_unit.as_ref())?;
+        let basis = LabelBasis::try_from(self.basis.as_ref())?;
+        let serving_size: Option<f64> =
+            errors.optional_positive("serving_size", &self.serving_size);
+        if serving_size.is_none()
+            && (basis == LabelBasis::PerServing || !self.serving_name.is_empty())
+        {
+            errors.add(
+                "serving_size",
+                "Required for values per serving, or to save a serving.",
+            );
+        }
+        let energy_unit = EnergyUnit::try_from(self.energy_unit.as_ref())?;
+        let label = Nutrition {
+            energy: energy_unit.to_kcal(errors.non_negative("energy", &self.energy)),
+            protein: errors.non_negative("protein", &self.protein),
+            fat: errors.non_negative("fat", &self.fat),
+            fat_saturated: errors.non_negative("fat_saturated", &self.fat_saturated),
+            carbs: errors.non_negative("carbs", &self.carbs),
+            carbs_sugars: errors.non_negative("carbs_sugars", &self.carbs_sugars),
+            fibre: errors.non_negative("fibre", &self.fibre),
+            sodium: errors.non_negative("sodium", &self.sodium),
+        };
+        let nutrition: Nutrition = if errors.is_empty() {
+            let nutrition = basis.to_per_100(label, serving_size)?;
+            nutrition.validate(serving_unit, errors);
+            nutrition
+        } else {
+            label
+        };
+        if errors.is_empty() && !self.confirmed && nutrition.energy_is_suspicious() {
+            errors.add(
+                "confirmed",
+                energy_warning(&label, energy_unit, basis, serving_unit),
+            );
+        }
+        Ok(ParsedFood {
+            serving_unit,
+            serving_size,
+            nutrition,
+        })
+    }
+
+    /// The warning about a suspicious energy value, with a checkbox to
+    /// confirm it. The checkbox stays once checked, so that fixing another
+    /// field doesn't mean confirming again.
+    pub fn energy_confirmation(&self, errors: &FieldErrors) -> Markup {
+        html! {
+            @if errors.get("confirmed").is_some() || self.confirmed {
+                (field_error(errors, "confirmed"))
+                .form-group {
+                    label {
+                        input type="checkbox" name="confirmed" value="true" checked[self.confirmed];
+                        " The energy is correct"
+                    }
+                }
+            }
+        }
+    }
+
+    /// The serving to save alongside the food, if one was named.
+    pub fn new_serving(&self, food: &ParsedFood, created_at: DateTime<Utc>) -> Option<NewServing> {
+        match food.serving_size {
+            Some(serving_amount) if !self.serving_name.is_empty() => Some(NewServing {
+                serving_name: self.serving_name.clone(),
+                serving_amount,
+                created_at,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Describe how far the stated energy is from the Atwater estimate, in the
+/// unit and basis the values were entered in.
+fn energy_warning(
+    label: &Nutrition,
+    energy_unit: EnergyUnit,
+    basis: LabelBasis,
+    serving_unit: BasicUnit,
+) -> String {
+    let per = match basis {
+        LabelBasis::Per100 => format!("per 100 {}", serving_unit.as_str()),
+        LabelBasis::PerServing => "per serving".to_string(),
+    };
+    let unit = energy_unit.as_str();
+    format!(
+        "The stated energy is {:.0} {unit} {per}, but the macronutrients add up to about {:.0} {unit}. This often means a typo, such as an extra zero. Check the values against the label, or confirm the energy is correct.",
+        energy_unit.convert_kcal(label.energy),
+        energy_unit.convert_kcal(label.atwater_energy()),
+    )
+}
@@ -13,16 +13,20 @@
 // limitations under the License.
 
 use axum::Router;
+use axum::extract::Query;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::Html;
 use axum::routing::get;
+use maud::Markup;
 use maud::html;
+use serde::Deserialize;
 
 use crate::db::FoodListEntry;
 use crate::error::Fallible;
 use crate::routes::food_new::FoodNewHandler;
 use crate::routes::food_view::FoodViewHandler;
+use crate::routes::recipe_new::RecipeNewHandler;
 use crate::ui::*;
 use crate::www::ServerState;
 
@@ -38,11 +42,45 @@ impl FoodListHandler {
     }
 }
 
-async fn handler(State(state): State<ServerState>) -> Fallible<(StatusCode, Html<String>)> {
-    let db = state.db.try_lock()?;
-    let foods: Vec<FoodListEntry> = db.list_foods()?;
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: Option<String>,
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Query(query): Query<SearchQuery>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    let q: String = query.q.unwrap_or_default();
+    state
+        .db
+        .run(move |db| {
+            let foods: Vec<FoodListEntry> = db.search_foods(&q)?;
+            let archived: Vec<FoodListEntry> = db.search_archived_foods(&q)?;
+
+            let content = html! {
+                .button-bar {
+                    a .button href=(FoodNewHandler::url()) { "Add New Food" }
+                    a .button href=(RecipeNewHandler::url()) { "Add New Recipe" }
+                }
+                (search_form(FoodListHandler::url(), &q))
+                (render_food_table(&foods))
+                @if !archived.is_empty() {
+                    h2 {
+                        "Archived"
+                    }
+                    (render_food_table(&archived))
+                }
+            };
 
-    let table_content = if foods.is_empty() {
+            let html_page = page("Food Library", content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+fn render_food_table(foods: &[FoodListEntry]) -> Markup {
+    if foods.is_empty() {
         html! {
             p {
                 "No foods."
@@ -62,7 +100,7 @@ async fn handler(State(state): State<ServerState>) -> Fallible<(StatusCode, Html
                     }
                 }
                 tbody {
-                    @for food in &foods {
+                    @for food in foods {
                         tr {
                             td {
                                 a href=(FoodViewHandler::url(food.food_id)) {
@@ -81,15 +119,5 @@ async fn handler(State(state): State<ServerState>) -> Fallible<(StatusCode, Html
                 }
             }
         }
-    };
-
-    let content = html! {
-        .button-bar {
-            a .button href=(FoodNewHandler::url()) { "Add New Food" }
-        }
-        (table_content)
-    };
-
-    let html_page = page("Food Library", content);
-    Ok((StatusCode::OK, Html(html_page.into_string())))
+    }
 }
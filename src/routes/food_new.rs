@@ -17,19 +17,27 @@ use axum::Router;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::Html;
+use axum::response::IntoResponse;
 use axum::response::Redirect;
+use axum::response::Response;
 use axum::routing::get;
 use axum::routing::post;
 use chrono::Utc;
 use maud::html;
-use serde::Deserialize;
 
 use crate::db::CreateFoodInput;
 use crate::db::FoodId;
+use crate::db::NewServing;
 use crate::error::Fallible;
+use crate::routes::food_form::FoodForm;
+use crate::routes::food_form::ParsedFood;
 use crate::routes::food_view::FoodViewHandler;
 use crate::types::BasicUnit;
+use crate::types::EnergyUnit;
+use crate::types::LabelBasis;
+use crate::types::Nutrition;
 use crate::ui::*;
+use crate::validation::FieldErrors;
 use crate::www::ServerState;
 
 pub struct FoodNewHandler {}
@@ -45,7 +53,22 @@ impl FoodNewHandler {
     }
 }
 
-async fn get_handler() -> Fallible<(StatusCode, Html<String>)> {
+async fn get_handler(State(state): State<ServerState>) -> Fallible<(StatusCode, Html<String>)> {
+    let form = FoodForm {
+        serving_unit: BasicUnit::Grams.as_str().to_string(),
+        energy_unit: state.energy_unit.as_str().to_string(),
+        basis: LabelBasis::Per100.as_str().to_string(),
+        ..FoodForm::default()
+    };
+    Ok((
+        StatusCode::OK,
+        render_form(&form, &FieldErrors::new(), state.energy_unit),
+    ))
+}
+
+/// Render the form, filled in with the given values and errors.
+fn render_form(form: &FoodForm, errors: &FieldErrors, energy_unit: EnergyUnit) -> Html<String> {
+    let energy_unit = EnergyUnit::try_from(form.energy_unit.as_str()).unwrap_or(energy_unit);
     let form_content = html! {
         form .main-form method="post" action=(FoodNewHandler::url()) {
             .form-section {
@@ -55,36 +78,41 @@ async fn get_handler() -> Fallible<(StatusCode, Html<String>)> {
                 (form_row(html! {
                     (form_group(html! {
                         (label_required("food_name", "Food Name"))
-                        (text_input("food_name", "food_name", "e.g., Rolled Oats"))
+                        (text_input_value("food_name", "food_name", &form.food_name, "e.g., Rolled Oats"))
+                        (field_error(errors, "food_name"))
                     }))
                 }))
                 (form_row(html! {
                     (form_group_half(html! {
                         (label_with_hint("brand", "Brand", "(optional, leave blank for generic foods)"))
-                        (text_input("brand", "brand", "e.g., Uncle Tobys"))
+                        (text_input_value("brand", "brand", &form.brand, "e.g., Uncle Tobys"))
                     }))
                     (form_group_half(html! {
                         (label_required("serving_unit", "Base Unit"))
-                        (select("serving_unit", "serving_unit", vec![
+                        (select_with_selected("serving_unit", "serving_unit", vec![
                             ("g".to_string(), "Grams (g)".to_string()),
                             ("ml".to_string(), "Milliliters (ml)".to_string()),
-                        ]))
+                        ], &form.serving_unit))
                     }))
                 }))
             }
+            (label_basis_section(&form.basis, &form.serving_size, &form.serving_name, errors))
             .form-section {
                 .form-section-title {
-                    "Nutrition Information (per 100g or 100ml)"
+                    "Nutrition Information"
                 }
+                (field_error(errors, "nutrition"))
                 (nutrition_table(html! {
-                    (nutrition_row("Energy", "energy", "energy", "kcal", 0))
-                    (nutrition_row("Protein", "protein", "protein", "g", 0))
-                    (nutrition_row("Fat, Total", "fat", "fat", "g", 0))
-                    (nutrition_row("Saturated", "fat_saturated", "fat_saturated", "g", 1))
-                    (nutrition_row("Carbohydrate", "carbs", "carbs", "g", 0))
-                    (nutrition_row("Sugars", "carbs_sugars", "carbs_sugars", "g", 1))
-                    (nutrition_row("Dietary Fibre", "fibre", "fibre", "g", 0))
-                    (nutrition_row("Sodium", "sodium", "sodium", "mg", 0))
+                    (energy_row("Energy", &form.energy, energy_unit))
+                    (field_error(errors, "energy"))
+                    (form.energy_confirmation(errors))
+                    (nutrition_row_checked("Protein", "protein", "g", &form.protein, 0, errors))
+                    (nutrition_row_checked("Fat, Total", "fat", "g", &form.fat, 0, errors))
+                    (nutrition_row_checked("Saturated", "fat_saturated", "g", &form.fat_saturated, 1, errors))
+                    (nutrition_row_checked("Carbohydrate", "carbs", "g", &form.carbs, 0, errors))
+                    (nutrition_row_checked("Sugars", "carbs_sugars", "g", &form.carbs_sugars, 1, errors))
+                    (nutrition_row_checked("Dietary Fibre", "fibre", "g", &form.fibre, 0, errors))
+                    (nutrition_row_checked("Sodium", "sodium", "mg", &form.sodium, 0, errors))
                 }))
             }
             .button-bar {
@@ -94,57 +122,42 @@ async fn get_handler() -> Fallible<(StatusCode, Html<String>)> {
     };
 
     let html_page = page("Add New Food", form_content);
-    Ok((StatusCode::OK, Html(html_page.into_string())))
-}
-
-#[derive(Deserialize)]
-struct CreateFoodForm {
-    food_name: String,
-    brand: String,
-    serving_unit: String,
-    energy: f64,
-    protein: f64,
-    fat: f64,
-    fat_saturated: f64,
-    carbs: f64,
-    carbs_sugars: f64,
-    fibre: f64,
-    sodium: f64,
+    Html(html_page.into_string())
 }
 
 async fn post_handler(
     State(state): State<ServerState>,
-    Form(form): Form<CreateFoodForm>,
-) -> Fallible<Redirect> {
-    let CreateFoodForm {
-        food_name,
-        brand,
-        serving_unit,
-        energy,
-        protein,
-        fat,
-        fat_saturated,
-        carbs,
-        carbs_sugars,
-        fibre,
-        sodium,
-    } = form;
+    Form(form): Form<FoodForm>,
+) -> Fallible<Response> {
+    let mut errors = FieldErrors::new();
+    let food: ParsedFood = form.parse_and_validate(&mut errors)?;
+    if !errors.is_empty() {
+        let html = render_form(&form, &errors, state.energy_unit);
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, html).into_response());
+    }
     let created_at = Utc::now();
+    let serving: Option<NewServing> = form.new_serving(&food, created_at);
+    let nutrition: Nutrition = food.nutrition;
     let input = CreateFoodInput {
-        name: food_name,
-        brand,
-        serving_unit: BasicUnit::try_from(serving_unit.as_ref())?,
-        energy,
-        protein,
-        fat,
-        fat_saturated,
-        carbs,
-        carbs_sugars,
-        fibre,
-        sodium,
+        name: form.food_name,
+        brand: form.brand,
+        serving_unit: food.serving_unit,
+        energy: nutrition.energy,
+        protein: nutrition.protein,
+        fat: nutrition.fat,
+        fat_saturated: nutrition.fat_saturated,
+        carbs: nutrition.carbs,
+        carbs_sugars: nutrition.carbs_sugars,
+        fibre: nutrition.fibre,
+        sodium: nutrition.sodium,
+        serving,
         created_at,
     };
-    let db = state.db.try_lock()?;
-    let food_id: FoodId = db.create_food(input)?;
-    Ok(Redirect::to(&FoodViewHandler::url(food_id)))
+    state
+        .db
+        .run(move |db| {
+            let food_id: FoodId = db.create_food(input)?;
+            Ok(Redirect::to(&FoodViewHandler::url(food_id)).into_response())
+        })
+        .await
 }
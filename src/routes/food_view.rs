@@ -18,15 +18,24 @@ use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::Html;
 use axum::routing::get;
+use maud::Markup;
 use maud::html;
 
 use crate::db::FoodEntry;
 use crate::db::FoodId;
+use crate::db::Ingredient;
+use crate::db::Recipe;
 use crate::db::Serving;
 use crate::error::Fallible;
+use crate::routes::food_archive::FoodArchiveHandler;
+use crate::routes::food_delete::FoodDeleteHandler;
 use crate::routes::food_edit::FoodEditHandler;
+use crate::routes::ingredient_delete::IngredientDeleteHandler;
+use crate::routes::ingredient_new::IngredientNewHandler;
+use crate::routes::recipe_edit::RecipeEditHandler;
 use crate::routes::serving_delete::ServingDeleteHandler;
 use crate::routes::serving_new::ServingNewHandler;
+use crate::types::EnergyUnit;
 use crate::ui::*;
 use crate::www::ServerState;
 
@@ -46,115 +55,224 @@ async fn handler(
     State(state): State<ServerState>,
     Path(food_id): Path<FoodId>,
 ) -> Fallible<(StatusCode, Html<String>)> {
-    let db = state.db.try_lock()?;
-    let food: FoodEntry = db.get_food(food_id)?;
-    let servings: Vec<Serving> = db.list_servings(food_id)?;
+    let energy_unit: EnergyUnit = state.energy_unit;
+    state
+        .db
+        .run(move |db| {
+            let food: FoodEntry = db.get_food(food_id)?;
+            let servings: Vec<Serving> = db.list_servings(food_id)?;
+            let recipe: Option<Recipe> = db.get_recipe(food_id)?;
+            let recipe_section = match &recipe {
+                Some(recipe) => {
+                    let ingredients: Vec<Ingredient> = db.list_ingredients(food_id)?;
+                    render_ingredients(
+                        recipe,
+                        &ingredients,
+                        food.serving_unit.as_str(),
+                        energy_unit,
+                    )
+                }
+                None => html! {},
+            };
 
-    let food_title = if food.brand.is_empty() {
-        food.name.clone()
-    } else {
-        format!("{} — {}", food.name, food.brand)
-    };
+            let food_title = if food.brand.is_empty() {
+                food.name.clone()
+            } else {
+                format!("{} — {}", food.name, food.brand)
+            };
 
-    // Nutrition table
-    let nutrition_table = html! {
-        table {
-            thead {
-                tr {
-                    th { "Nutrient" }
-                    th.numeric { "Per 100" (food.serving_unit.as_str()) }
-                }
-            }
-            tbody {
-                tr {
-                    td { "Energy" }
-                    td.numeric { (format!("{:.1} kcal", food.energy)) }
-                }
-                tr {
-                    td { "Protein" }
-                    td.numeric { (format!("{:.1} g", food.protein)) }
+            // Nutrition table
+            let nutrition_table = html! {
+                table {
+                    thead {
+                        tr {
+                            th { "Nutrient" }
+                            th.numeric { "Per 100" (food.serving_unit.as_str()) }
+                        }
+                    }
+                    tbody {
+                        tr {
+                            td { "Energy" }
+                            td.numeric { (format!("{:.1} {}", energy_unit.convert_kcal(food.energy), energy_unit.as_str())) }
+                        }
+                        tr {
+                            td { "Protein" }
+                            td.numeric { (format!("{:.1} g", food.protein)) }
+                        }
+                        tr {
+                            td { "Fat, Total" }
+                            td.numeric { (format!("{:.1} g", food.fat)) }
+                        }
+                        tr {
+                            td { "— Saturated" }
+                            td.numeric { (format!("{:.1} g", food.fat_saturated)) }
+                        }
+                        tr {
+                            td { "Carbohydrate" }
+                            td.numeric { (format!("{:.1} g", food.carbs)) }
+                        }
+                        tr {
+                            td { "— Sugars" }
+                            td.numeric { (format!("{:.1} g", food.carbs_sugars)) }
+                        }
+                        tr {
+                            td { "Dietary Fibre" }
+                            td.numeric { (format!("{:.1} g", food.fibre)) }
+                        }
+                        tr {
+                            td { "Sodium" }
+                            td.numeric { (format!("{:.0} mg", food.sodium)) }
+                        }
+                    }
                 }
-                tr {
-                    td { "Fat, Total" }
-                    td.numeric { (format!("{:.1} g", food.fat)) }
+            };
+
+            let content = html! {
+                .button-bar {
+                    @if recipe.is_some() {
+                        a .button href=(RecipeEditHandler::url(food_id)) {
+                            "Edit Recipe"
+                        }
+                    } @else {
+                        a .button href=(FoodEditHandler::url(food_id)) {
+                            "Edit Food"
+                        }
+                    }
+                    form method="post" action=(FoodArchiveHandler::url(food_id)) {
+                        @if food.archived {
+                            input type="hidden" name="archived" value="false";
+                            input .button type="submit" value="Unarchive";
+                        } @else {
+                            input type="hidden" name="archived" value="true";
+                            input .button type="submit" value="Archive";
+                        }
+                    }
+                    a .button href=(FoodDeleteHandler::url(food_id)) {
+                        "Delete"
+                    }
                 }
-                tr {
-                    td { "— Saturated" }
-                    td.numeric { (format!("{:.1} g", food.fat_saturated)) }
+                @if food.archived {
+                    p {
+                        "This food is archived: it is hidden when logging food and adding ingredients."
+                    }
                 }
-                tr {
-                    td { "Carbohydrate" }
-                    td.numeric { (format!("{:.1} g", food.carbs)) }
+                (nutrition_table)
+                (recipe_section)
+                h2 {
+                    "Custom Serving Sizes"
                 }
-                tr {
-                    td { "— Sugars" }
-                    td.numeric { (format!("{:.1} g", food.carbs_sugars)) }
+                table {
+                    thead {
+                        tr {
+                            th { "Name" }
+                            th { "Equals" }
+                            th { "Delete" }
+                        }
+                    }
+                    tbody {
+                        @for serving in &servings {
+                            tr {
+                                td {
+                                    (serving.serving_name)
+                                }
+                                td {
+                                    (serving.serving_amount) (food.serving_unit.as_str())
+                                }
+                                td {
+                                    a .button href=(ServingDeleteHandler::url(food_id, serving.serving_id)) {
+                                        "Delete"
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }
-                tr {
-                    td { "Dietary Fibre" }
-                    td.numeric { (format!("{:.1} g", food.fibre)) }
+
+                h2 {
+                    "Add Custom Serving Size"
                 }
-                tr {
-                    td { "Sodium" }
-                    td.numeric { (format!("{:.0} mg", food.sodium)) }
+                form method="post" action=(ServingNewHandler::url(food_id)) {
+                    .form-group {
+                        (label("serving_name", "Serving Name"))
+                        (text_input("serving_name", "serving_name", "e.g., cup, slice, package"))
+                    }
+                    .form-group {
+                        (label("serving_amount", &format!("Amount ({})", food.serving_unit.as_str())))
+                        (number_input("serving_amount", "serving_amount", "0.1", "e.g., 250"))
+                    }
+                    input .button type="submit" value="Add Serving";
                 }
-            }
-        }
-    };
+            };
 
-    let content = html! {
-        .button-bar {
-            a .button href=(FoodEditHandler::url(food_id)) {
-                "Edit Food"
-            }
-        }
-        (nutrition_table)
+            let html_page = page(&food_title.to_string(), content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+fn render_ingredients(
+    recipe: &Recipe,
+    ingredients: &[Ingredient],
+    unit: &str,
+    energy_unit: EnergyUnit,
+) -> Markup {
+    let recipe_id = recipe.food_id;
+    html! {
         h2 {
-            "Custom Serving Sizes"
+            "Ingredients"
         }
-        table {
-            thead {
-                tr {
-                    th { "Name" }
-                    th { "Equals" }
-                    th { "Delete" }
-                }
+        p {
+            "Total cooked weight: " (format!("{:.0}", recipe.cooked_weight)) (unit)
+        }
+        @if ingredients.is_empty() {
+            p {
+                "No ingredients."
             }
-            tbody {
-                @for serving in &servings {
+        } @else {
+            table {
+                thead {
                     tr {
-                        td {
-                            (serving.serving_name)
-                        }
-                        td {
-                            (serving.serving_amount) (food.serving_unit.as_str())
-                        }
-                        td {
-                            form method="post" action=(ServingDeleteHandler::url(food_id, serving.serving_id)) {
-                                input .button type="submit" value="Delete";
+                        th { "Food" }
+                        th { "Amount" }
+                        th .numeric { "Energy (" (energy_unit.as_str()) ")" }
+                        th .numeric { "Protein (g)" }
+                        th { "Delete" }
+                    }
+                }
+                tbody {
+                    @for ingredient in ingredients {
+                        tr {
+                            td {
+                                a href=(FoodViewHandler::url(ingredient.food_id)) {
+                                    (ingredient.name)
+                                }
+                                @if !ingredient.brand.is_empty() {
+                                    " — " (ingredient.brand)
+                                }
+                            }
+                            td {
+                                (format!("{} {}", ingredient.amount, ingredient.serving_name.as_deref().unwrap_or(ingredient.serving_unit.as_str())))
+                            }
+                            td .numeric {
+                                (format!("{:.0}", energy_unit.convert_kcal(ingredient.nutrition.energy)))
+                            }
+                            td .numeric {
+                                (format!("{:.1}", ingredient.nutrition.protein))
+                            }
+                            td {
+                                form method="post" action=(IngredientDeleteHandler::url(recipe_id, ingredient.ingredient_id)) {
+                                    input .button type="submit" value="Delete";
+                                }
                             }
                         }
                     }
                 }
             }
         }
-
-        h2 {
-            "Add Custom Serving Size"
-        }
-        form method="post" action=(ServingNewHandler::url(food_id)) {
-            .form-group {
-                (label("serving_name", "Serving Name"))
-                (text_input("serving_name", "serving_name", "e.g., cup, slice, package"))
-            }
-            .form-group {
-                (label("serving_amount", &format!("Amount ({})", food.serving_unit.as_str())))
-                (number_input("serving_amount", "serving_amount", "0.1", "e.g., 250"))
+        .button-bar {
+            a .button href=(IngredientNewHandler::url(recipe_id)) {
+                "Add Ingredient"
             }
-            input .button type="submit" value="Add Serving";
         }
-    };
-
-    let html_page = page(&food_title.to_string(), content);
-    Ok((StatusCode::OK, Html(html_page.into_string())))
+    }
 }
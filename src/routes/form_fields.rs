@@ -0,0 +1,45 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use serde::Deserialize;
+
+use crate::db::EntryId;
+use crate::error::Fallible;
+
+/// The fields of a submitted form, in order. Forms with a checkbox per entry
+/// repeat `entry_id` once per checked entry, which a struct can't capture, so
+/// those handlers extract this instead.
+#[derive(Deserialize)]
+#[serde(transparent)]
+pub struct FormFields(Vec<(String, String)>);
+
+impl FormFields {
+    /// The value of a field. If the field is repeated, the first value.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The IDs of every checked entry.
+    pub fn entry_ids(&self) -> Fallible<Vec<EntryId>> {
+        let mut entry_ids: Vec<EntryId> = Vec::new();
+        for (key, value) in &self.0 {
+            if key == "entry_id" {
+                entry_ids.push(value.parse::<EntryId>()?);
+            }
+        }
+        Ok(entry_ids)
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::response::Redirect;
+use axum::routing::post;
+
+use crate::db::FoodId;
+use crate::db::IngredientId;
+use crate::error::Fallible;
+use crate::routes::food_view::FoodViewHandler;
+use crate::www::ServerState;
+
+pub struct IngredientDeleteHandler {}
+
+impl IngredientDeleteHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route(
+            "/library/{food_id}/ingredients/{ingredient_id}/delete",
+            post(handler),
+        )
+    }
+
+    pub fn url(recipe_id: FoodId, ingredient_id: IngredientId) -> String {
+        format!("/library/{recipe_id}/ingredients/{ingredient_id}/delete")
+    }
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Path((recipe_id, ingredient_id)): Path<(FoodId, IngredientId)>,
+) -> Fallible<Redirect> {
+    state
+        .db
+        .run(move |db| {
+            db.delete_ingredient(recipe_id, ingredient_id)?;
+            Ok(Redirect::to(&FoodViewHandler::url(recipe_id)))
+        })
+        .await
+}
@@ -0,0 +1,211 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::response::Redirect;
+use axum::routing::get;
+use axum::routing::post;
+use chrono::Utc;
+use maud::html;
+use serde::Deserialize;
+
+use crate::db::CreateIngredientInput;
+use crate::db::FoodId;
+use crate::db::ServingId;
+use crate::error::Fallible;
+use crate::routes::food_view::FoodViewHandler;
+use crate::ui::*;
+use crate::validation::check_positive;
+use crate::www::ServerState;
+
+pub struct IngredientNewHandler {}
+
+impl IngredientNewHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route("/library/{food_id}/ingredients/new", get(get_handler));
+        let router = router.route(
+            "/library/{food_id}/ingredients/new/food/{ingredient_food_id}",
+            get(get_handler_with_food_id),
+        );
+        router.route(
+            "/library/{food_id}/ingredients/new/food/{ingredient_food_id}",
+            post(post_handler),
+        )
+    }
+
+    pub fn url(recipe_id: FoodId) -> String {
+        format!("/library/{recipe_id}/ingredients/new")
+    }
+
+    pub fn url_with_food_id(recipe_id: FoodId, food_id: FoodId) -> String {
+        format!("/library/{recipe_id}/ingredients/new/food/{food_id}")
+    }
+}
+
+async fn get_handler(
+    State(state): State<ServerState>,
+    Path(recipe_id): Path<FoodId>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    state
+        .db
+        .run(move |db| {
+            let recipe = db.get_food(recipe_id)?;
+            let foods = db.list_foods()?;
+
+            let table_content = if foods.is_empty() {
+                html! {
+                    p {
+                        "No foods."
+                    }
+                }
+            } else {
+                html! {
+                    table {
+                        thead {
+                            tr {
+                                th {
+                                    "Name"
+                                }
+                                th {
+                                    "Brand"
+                                }
+                            }
+                        }
+                        tbody {
+                            @for food in foods.iter().filter(|food| food.food_id != recipe_id) {
+                                tr {
+                                    td {
+                                        a href=(IngredientNewHandler::url_with_food_id(recipe_id, food.food_id)) {
+                                            (food.name)
+                                        }
+                                    }
+                                    td {
+                                        @if food.brand.is_empty() {
+                                            "—"
+                                        } @else {
+                                            (food.brand)
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+
+            let html_page = page(&format!("Add Ingredient to {}", recipe.name), table_content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+async fn get_handler_with_food_id(
+    State(state): State<ServerState>,
+    Path((recipe_id, food_id)): Path<(FoodId, FoodId)>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    state
+        .db
+        .run(move |db| {
+            let recipe = db.get_food(recipe_id)?;
+            let food = db.get_food(food_id)?;
+            let servings = db.list_servings(food_id)?;
+
+            let food_title = if food.brand.is_empty() {
+                food.name.clone()
+            } else {
+                format!("{} — {}", food.name, food.brand)
+            };
+
+            let mut options: Vec<(String, String)> = Vec::new();
+            for serving in &servings {
+                options.push((
+                    serving.serving_id.to_string(),
+                    format!(
+                        "{} ({} {})",
+                        serving.serving_name,
+                        serving.serving_amount,
+                        food.serving_unit.as_str()
+                    ),
+                ));
+            }
+            options.push(("".to_string(), food.serving_unit.as_str().to_owned()));
+
+            let form_content = html! {
+                form .main-form method="post" action=(IngredientNewHandler::url_with_food_id(recipe_id, food_id)) {
+                    .form-group {
+                        label .label-required for="amount" { "Amount" }
+                        input .narrow type="number" id="amount" name="amount" step="0.1" placeholder="e.g., 250";
+                    }
+                    .form-group {
+                        label .label-required for="serving_id" { "Unit" }
+                        select .narrow id="serving_id" name="serving_id" {
+                            @for (value, label) in options {
+                                option value=(value) { (label) }
+                            }
+                        }
+                    }
+                    .button-bar {
+                        input .button type="submit" value="Add Ingredient";
+                    }
+                }
+            };
+
+            let html_page = page(
+                &format!("Add {} to {}", food_title, recipe.name),
+                form_content,
+            );
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+#[derive(Deserialize)]
+struct CreateIngredientForm {
+    serving_id: String,
+    amount: f64,
+}
+
+async fn post_handler(
+    State(state): State<ServerState>,
+    Path((recipe_id, food_id)): Path<(FoodId, FoodId)>,
+    Form(form): Form<CreateIngredientForm>,
+) -> Fallible<Redirect> {
+    let serving_id = if form.serving_id.is_empty() {
+        None
+    } else {
+        Some(form.serving_id.parse::<ServingId>()?)
+    };
+
+    let input = CreateIngredientInput {
+        recipe_id,
+        food_id,
+        serving_id,
+        amount: check_positive("Amount", form.amount)?,
+        created_at: Utc::now(),
+    };
+
+    state
+        .db
+        .run(move |db| {
+            db.create_ingredient(input)?;
+
+            Ok(Redirect::to(&FoodViewHandler::url(recipe_id)))
+        })
+        .await
+}
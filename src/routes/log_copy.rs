@@ -0,0 +1,231 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::response::Redirect;
+use axum::routing::get;
+use axum::routing::post;
+use chrono::Utc;
+use maud::Markup;
+use maud::html;
+use serde::Deserialize;
+
+use crate::db::CopyEntriesInput;
+use crate::db::EntryId;
+use crate::db::EntrySelection;
+use crate::db::LogEntry;
+use crate::db::MealSlot;
+use crate::db::SlotId;
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::routes::form_fields::FormFields;
+use crate::routes::log_view::LogViewHandler;
+use crate::types::Date;
+use crate::ui::*;
+use crate::www::ServerState;
+
+/// Copies entries from another day.
+pub struct LogCopyHandler {}
+
+impl LogCopyHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route("/log/{date}/copy", get(get_handler));
+        router.route("/log/{date}/copy", post(post_handler))
+    }
+
+    pub fn url(date: Date) -> String {
+        format!("/log/{date}/copy")
+    }
+}
+
+#[derive(Deserialize)]
+struct CopyQuery {
+    /// The day to copy from. Defaults to the day before.
+    from: Option<String>,
+}
+
+async fn get_handler(
+    State(state): State<ServerState>,
+    Path(date): Path<String>,
+    Query(query): Query<CopyQuery>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    let date = Date::try_from(date)?;
+    let from: Date = match query.from {
+        Some(from) if !from.is_empty() => Date::try_from(from)?,
+        _ => date.prev_day(),
+    };
+    state
+        .db
+        .run(move |db| {
+            let entries: Vec<LogEntry> = db.list_log_entries(from, from)?;
+            let slots: Vec<MealSlot> = db.list_meal_slots()?;
+            let content = html! {
+                form .search-form method="get" action=(LogCopyHandler::url(date)) {
+                    (label("from", "Copy from"))
+                    (date_input_value("from", "from", &from.to_string()))
+                    input .button type="submit" value="Show";
+                }
+                @if entries.is_empty() {
+                    p {
+                        "No food logged for " (from.humanize()) "."
+                    }
+                } @else {
+                    (render_entries(date, from, &entries, &slots))
+                }
+                .button-bar {
+                    a .button href=(LogViewHandler::url(date)) {
+                        "Cancel"
+                    }
+                }
+            };
+            let title = format!("Copy Entries to {}", date.humanize());
+            let html_page = page(&title, content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+/// Render the entries of the source day, with a button to copy each meal, and
+/// a checkbox on each entry to copy a selection of them.
+fn render_entries(date: Date, from: Date, entries: &[LogEntry], slots: &[MealSlot]) -> Markup {
+    let mut meals: Vec<(String, Option<SlotId>)> = slots
+        .iter()
+        .map(|slot| (slot.name.clone(), Some(slot.slot_id)))
+        .collect();
+    meals.push(("Other".to_string(), None));
+    html! {
+        // The checkboxes live in the table, and refer to this form by ID, since
+        // forms can't be nested.
+        form #copy-selected method="POST" action=(LogCopyHandler::url(date)) {
+            input type="hidden" name="from" value=(from);
+            input type="hidden" name="scope" value="selected";
+        }
+        table {
+            thead {
+                tr {
+                    th {}
+                    th {
+                        "Time"
+                    }
+                    th {
+                        "Food"
+                    }
+                    th {
+                        "Brand"
+                    }
+                    th {
+                        "Amount"
+                    }
+                }
+            }
+            tbody {
+                @for (name, slot_id) in &meals {
+                    @let meal: Vec<&LogEntry> = entries.iter().filter(|e| e.entry.slot_id == *slot_id).collect();
+                    @if !meal.is_empty() {
+                        tr .meal-header {
+                            td colspan="4" {
+                                (name)
+                            }
+                            td .center {
+                                form method="POST" action=(LogCopyHandler::url(date)) {
+                                    input type="hidden" name="from" value=(from);
+                                    input type="hidden" name="scope" value="slot";
+                                    input type="hidden" name="slot_id" value=(slot_id.map(|id| id.to_string()).unwrap_or_default());
+                                    input .button type="submit" value=(format!("Copy {name}"));
+                                }
+                            }
+                        }
+                        @for log_entry in meal {
+                            tr {
+                                td .center {
+                                    input type="checkbox" form="copy-selected" name="entry_id" value=(log_entry.entry.entry_id) checked;
+                                }
+                                td .center {
+                                    (log_entry.entry.eaten_at)
+                                }
+                                td {
+                                    (log_entry.food_name)
+                                }
+                                td {
+                                    @if log_entry.food_brand.is_empty() {
+                                        "—"
+                                    } @else {
+                                        (log_entry.food_brand)
+                                    }
+                                }
+                                td {
+                                    (log_entry.describe_amount())
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        .button-bar {
+            input .button type="submit" form="copy-selected" value="Copy Selected";
+            form method="POST" action=(LogCopyHandler::url(date)) {
+                input type="hidden" name="from" value=(from);
+                input type="hidden" name="scope" value="all";
+                input .button type="submit" value="Copy All";
+            }
+        }
+    }
+}
+
+/// Copy the entries picked by one of the forms.
+async fn post_handler(
+    State(state): State<ServerState>,
+    Path(date): Path<String>,
+    Form(fields): Form<FormFields>,
+) -> Fallible<Redirect> {
+    let date = Date::try_from(date)?;
+    let from: Date = match fields.get("from") {
+        Some(from) => Date::try_from(from.to_string())?,
+        None => return Err(AppError::bad_request("Missing source date.")),
+    };
+    let selection: EntrySelection = match fields.get("scope") {
+        Some("all") => EntrySelection::All,
+        Some("slot") => match fields.get("slot_id") {
+            Some("") | None => EntrySelection::Slot(None),
+            Some(slot_id) => EntrySelection::Slot(Some(slot_id.parse::<SlotId>()?)),
+        },
+        Some("selected") => {
+            let entry_ids: Vec<EntryId> = fields.entry_ids()?;
+            if entry_ids.is_empty() {
+                return Err(AppError::bad_request("No entries selected."));
+            }
+            EntrySelection::Entries(entry_ids)
+        }
+        _ => return Err(AppError::bad_request("Invalid copy scope.")),
+    };
+    state
+        .db
+        .run(move |db| {
+            db.copy_entries(CopyEntriesInput {
+                from,
+                to: date,
+                selection,
+                created_at: Utc::now(),
+            })?;
+            Ok(Redirect::to(&LogViewHandler::url(date)))
+        })
+        .await
+}
@@ -41,7 +41,11 @@ async fn post_handler(
     Path((date, entry_id)): Path<(String, EntryId)>,
 ) -> Fallible<Redirect> {
     let date = Date::try_from(date)?;
-    let db = state.db.try_lock()?;
-    db.delete_entry(entry_id)?;
-    Ok(Redirect::to(&LogViewHandler::url(date)))
+    state
+        .db
+        .run(move |db| {
+            db.delete_entry(entry_id)?;
+            Ok(Redirect::to(&LogViewHandler::url(date)))
+        })
+        .await
 }
@@ -0,0 +1,345 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::response::IntoResponse;
+use axum::response::Redirect;
+use axum::response::Response;
+use axum::routing::get;
+use axum::routing::post;
+use maud::html;
+use serde::Deserialize;
+
+use crate::db::Db;
+use crate::db::EditEntryInput;
+use crate::db::Entry;
+use crate::db::EntryId;
+use crate::db::FoodId;
+use crate::db::ServingId;
+use crate::db::SlotId;
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::routes::log_view::LogViewHandler;
+use crate::types::Date;
+use crate::types::Time;
+use crate::ui::*;
+use crate::validation::FieldErrors;
+use crate::www::ServerState;
+
+pub struct LogEditHandler {}
+
+impl LogEditHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route("/log/{date}/entry/{entry_id}/edit", get(get_handler));
+        let router = router.route(
+            "/log/{date}/entry/{entry_id}/edit/food",
+            get(get_handler_food_picker),
+        );
+        router.route("/log/{date}/entry/{entry_id}/edit", post(post_handler))
+    }
+
+    pub fn url(date: Date, entry_id: EntryId) -> String {
+        format!("/log/{date}/entry/{entry_id}/edit")
+    }
+
+    pub fn url_with_food_id(date: Date, entry_id: EntryId, food_id: FoodId) -> String {
+        format!("/log/{date}/entry/{entry_id}/edit?food_id={food_id}")
+    }
+
+    pub fn url_food_picker(date: Date, entry_id: EntryId) -> String {
+        format!("/log/{date}/entry/{entry_id}/edit/food")
+    }
+}
+
+#[derive(Deserialize)]
+struct EditEntryQuery {
+    /// If present, the entry is being switched to this food.
+    food_id: Option<FoodId>,
+}
+
+async fn get_handler(
+    State(state): State<ServerState>,
+    Path((date, entry_id)): Path<(String, EntryId)>,
+    Query(query): Query<EditEntryQuery>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    let date = Date::try_from(date)?;
+    state
+        .db
+        .run(move |db| {
+            let entry: Entry = db.get_entry(entry_id)?;
+            let Some(entry_food_id) = entry.food_id else {
+                return Err(AppError::bad_request(
+                    "Quick-add entries can't be edited. Delete the entry and add it again instead.",
+                ));
+            };
+            let food_id: FoodId = query.food_id.unwrap_or(entry_food_id);
+            // When switching foods, the old serving doesn't apply.
+            let serving_id: String = match entry.serving_id {
+                Some(serving_id) if food_id == entry_food_id => serving_id.to_string(),
+                _ => "".to_string(),
+            };
+            let form = EditEntryForm {
+                food_id,
+                serving_id,
+                amount: entry.amount.to_string(),
+                date: entry.date.to_string(),
+                eaten_at: entry.eaten_at.to_string(),
+                slot_id: entry.slot_id.map(|id| id.to_string()).unwrap_or_default(),
+            };
+            let html = render_form(db, date, entry_id, &form, &FieldErrors::new())?;
+            Ok((StatusCode::OK, html))
+        })
+        .await
+}
+
+/// Render the form, filled in with the given values and errors.
+fn render_form(
+    db: &Db,
+    date: Date,
+    entry_id: EntryId,
+    form: &EditEntryForm,
+    errors: &FieldErrors,
+) -> Fallible<Html<String>> {
+    let food_id = form.food_id;
+    let food = db.get_food(food_id)?;
+    let servings = db.list_servings(food_id)?;
+    let slots = db.list_meal_slots()?;
+
+    let food_title = if food.brand.is_empty() {
+        food.name.clone()
+    } else {
+        format!("{} — {}", food.name, food.brand)
+    };
+
+    let mut options: Vec<(String, String)> = Vec::new();
+    for serving in &servings {
+        options.push((
+            serving.serving_id.to_string(),
+            format!(
+                "{} ({} {})",
+                serving.serving_name,
+                serving.serving_amount,
+                food.serving_unit.as_str()
+            ),
+        ));
+    }
+    options.push(("".to_string(), food.serving_unit.as_str().to_owned()));
+
+    let form_content = html! {
+        form .main-form method="post" action=(LogEditHandler::url(date, entry_id)) {
+            input type="hidden" name="food_id" value=(food_id.to_string());
+            .form-group {
+                label { "Food" }
+                p {
+                    (food_title)
+                    " "
+                    a href=(LogEditHandler::url_food_picker(date, entry_id)) { "(change)" }
+                }
+            }
+            .form-group {
+                label .label-required for="amount" { "Amount" }
+                input .narrow type="number" id="amount" name="amount" step="any" value=(form.amount);
+                (field_error(errors, "amount"))
+            }
+            .form-group {
+                label .label-required for="serving_id" { "Unit" }
+                select .narrow id="serving_id" name="serving_id" {
+                    @for (value, label) in options {
+                        @if value == form.serving_id {
+                            option value=(value) selected { (label) }
+                        } @else {
+                            option value=(value) { (label) }
+                        }
+                    }
+                }
+            }
+            .form-group {
+                label .label-required for="date" { "Date" }
+                input .narrow type="date" id="date" name="date" value=(form.date);
+                (field_error(errors, "date"))
+            }
+            .form-group {
+                label .label-required for="eaten_at" { "Time Eaten" }
+                input .narrow type="time" id="eaten_at" name="eaten_at" value=(form.eaten_at);
+                (field_error(errors, "eaten_at"))
+            }
+            .form-group {
+                label for="slot_id" { "Meal" }
+                select .narrow id="slot_id" name="slot_id" {
+                    @for slot in &slots {
+                        @if slot.slot_id.to_string() == form.slot_id {
+                            option value=(slot.slot_id) selected { (slot.name) }
+                        } @else {
+                            option value=(slot.slot_id) { (slot.name) }
+                        }
+                    }
+                    @if form.slot_id.is_empty() {
+                        option value="" selected { "None" }
+                    } @else {
+                        option value="" { "None" }
+                    }
+                }
+            }
+            .button-bar {
+                input .button type="submit" value="Save";
+                a .button href=(LogViewHandler::url(date)) { "Cancel" }
+            }
+        }
+    };
+
+    let html_page = page(&format!("Edit Entry: {}", food_title), form_content);
+    Ok(Html(html_page.into_string()))
+}
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: Option<String>,
+}
+
+async fn get_handler_food_picker(
+    State(state): State<ServerState>,
+    Path((date, entry_id)): Path<(String, EntryId)>,
+    Query(query): Query<SearchQuery>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    let q: String = query.q.unwrap_or_default();
+    let date = Date::try_from(date)?;
+    state
+        .db
+        .run(move |db| {
+            let foods = db.search_foods(&q)?;
+
+            let table_content = if foods.is_empty() {
+                html! {
+                    p {
+                        "No matching foods."
+                    }
+                }
+            } else {
+                html! {
+                    table {
+                        thead {
+                            tr {
+                                th {
+                                    "Name"
+                                }
+                                th {
+                                    "Brand"
+                                }
+                            }
+                        }
+                        tbody {
+                            @for food in &foods {
+                                tr {
+                                    td {
+                                        a href=(LogEditHandler::url_with_food_id(date, entry_id, food.food_id)) {
+                                            (food.name)
+                                        }
+                                    }
+                                    td {
+                                        @if food.brand.is_empty() {
+                                            "—"
+                                        } @else {
+                                            (food.brand)
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+
+            let content = html! {
+                (search_form(&LogEditHandler::url_food_picker(date, entry_id), &q))
+                (table_content)
+            };
+            let html_page = page("Change Food", content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+#[derive(Deserialize)]
+struct EditEntryForm {
+    food_id: FoodId,
+    serving_id: String,
+    amount: String,
+    date: String,
+    eaten_at: String,
+    slot_id: String,
+}
+
+async fn post_handler(
+    State(state): State<ServerState>,
+    Path((path_date, entry_id)): Path<(String, EntryId)>,
+    Form(form): Form<EditEntryForm>,
+) -> Fallible<Response> {
+    let path_date = Date::try_from(path_date)?;
+
+    let mut errors = FieldErrors::new();
+    let amount: f64 = errors.positive("amount", &form.amount);
+    let date: Option<Date> = match Date::try_from(form.date.clone()) {
+        Ok(date) => Some(date),
+        Err(_) => {
+            errors.add("date", "Must be a date.");
+            None
+        }
+    };
+    let eaten_at: Option<Time> = match Time::try_from(form.eaten_at.clone()) {
+        Ok(time) => Some(time),
+        Err(_) => {
+            errors.add("eaten_at", "Must be a time.");
+            None
+        }
+    };
+    state
+        .db
+        .run(move |db| {
+            let (Some(date), Some(eaten_at), true) = (date, eaten_at, errors.is_empty()) else {
+                let html = render_form(db, path_date, entry_id, &form, &errors)?;
+                return Ok((StatusCode::UNPROCESSABLE_ENTITY, html).into_response());
+            };
+
+            let serving_id = if form.serving_id.is_empty() {
+                None
+            } else {
+                Some(form.serving_id.parse::<ServingId>()?)
+            };
+
+            let input = EditEntryInput {
+                entry_id,
+                date,
+                food_id: form.food_id,
+                serving_id,
+                amount,
+                eaten_at,
+                slot_id: if form.slot_id.is_empty() {
+                    None
+                } else {
+                    Some(form.slot_id.parse::<SlotId>()?)
+                },
+            };
+
+            db.edit_entry(input)?;
+
+            Ok(Redirect::to(&LogViewHandler::url(date)).into_response())
+        })
+        .await
+}
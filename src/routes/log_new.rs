@@ -15,25 +15,39 @@
 use axum::Form;
 use axum::Router;
 use axum::extract::Path;
+use axum::extract::Query;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::Html;
+use axum::response::IntoResponse;
 use axum::response::Redirect;
+use axum::response::Response;
 use axum::routing::get;
 use axum::routing::post;
 use chrono::Utc;
+use maud::Markup;
 use maud::html;
 use serde::Deserialize;
 
 use crate::db::CreateEntryInput;
+use crate::db::Db;
 use crate::db::FoodId;
+use crate::db::LoggedFood;
+use crate::db::MealSlot;
 use crate::db::ServingId;
+use crate::db::SlotId;
 use crate::error::Fallible;
+use crate::routes::log_template::LogTemplateHandler;
 use crate::routes::log_view::LogViewHandler;
 use crate::types::Date;
+use crate::types::Time;
 use crate::ui::*;
+use crate::validation::FieldErrors;
 use crate::www::ServerState;
 
+/// How many foods to show in each of the recent and frequent sections.
+const SHORTCUT_LIMIT: usize = 10;
+
 pub struct LogNewHandler {}
 
 impl LogNewHandler {
@@ -55,69 +69,211 @@ impl LogNewHandler {
     }
 }
 
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: Option<String>,
+}
+
 async fn get_handler(
     State(state): State<ServerState>,
     Path(date): Path<String>,
+    Query(query): Query<SearchQuery>,
 ) -> Fallible<(StatusCode, Html<String>)> {
-    let db = state.db.try_lock()?;
-    let foods = db.list_foods()?;
-    let date = Date::try_from(date)?;
+    let q: String = query.q.unwrap_or_default();
+    state
+        .db
+        .run(move |db| {
+            let foods = db.search_foods(&q)?;
+            let date = Date::try_from(date)?;
 
-    let table_content = if foods.is_empty() {
-        html! {
-            p {
-                "No foods."
-            }
-        }
-    } else {
-        html! {
-            table {
-                thead {
-                    tr {
-                        th {
-                            "Name"
-                        }
-                        th {
-                            "Brand"
-                        }
+            let table_content = if foods.is_empty() {
+                html! {
+                    p {
+                        "No matching foods."
                     }
                 }
-                tbody {
-                    @for food in &foods {
-                        tr {
-                            td {
-                                a href=(LogNewHandler::url_with_food_id(date, food.food_id)) {
-                                    (food.name)
+            } else {
+                html! {
+                    table {
+                        thead {
+                            tr {
+                                th {
+                                    "Name"
+                                }
+                                th {
+                                    "Brand"
                                 }
                             }
-                            td {
-                                @if food.brand.is_empty() {
-                                    "—"
-                                } @else {
-                                    (food.brand)
+                        }
+                        tbody {
+                            @for food in &foods {
+                                tr {
+                                    td {
+                                        a href=(LogNewHandler::url_with_food_id(date, food.food_id)) {
+                                            (food.name)
+                                        }
+                                    }
+                                    td {
+                                        @if food.brand.is_empty() {
+                                            "—"
+                                        } @else {
+                                            (food.brand)
+                                        }
+                                    }
                                 }
                             }
                         }
                     }
                 }
-            }
-        }
-    };
+            };
 
-    let content = table_content;
+            // Shortcuts only make sense when browsing, not when searching.
+            let content = if q.trim().is_empty() {
+                let slots: Vec<MealSlot> = db.list_meal_slots()?;
+                let eaten_at: Time = default_eaten_at(date);
+                let slot_id: Option<SlotId> = MealSlot::default_for(&slots, eaten_at);
+                let recent: Vec<LoggedFood> = db.list_recent_foods(SHORTCUT_LIMIT)?;
+                // Don't repeat the recent foods.
+                let frequent: Vec<LoggedFood> = db
+                    .list_frequent_foods(date, eaten_at, SHORTCUT_LIMIT * 2)?
+                    .into_iter()
+                    .filter(|food| !recent.iter().any(|r| r.food_id == food.food_id))
+                    .take(SHORTCUT_LIMIT)
+                    .collect();
+                html! {
+                    .button-bar {
+                        a .button href=(LogTemplateHandler::url(date)) {
+                            "Log a Template"
+                        }
+                    }
+                    (search_form(&LogNewHandler::url(date), &q))
+                    @if !recent.is_empty() {
+                        h2 { "Recent" }
+                        (render_shortcuts(date, eaten_at, slot_id, &recent))
+                    }
+                    @if !frequent.is_empty() {
+                        h2 { "Frequent" }
+                        (render_shortcuts(date, eaten_at, slot_id, &frequent))
+                    }
+                    h2 { "All Foods" }
+                    (table_content)
+                }
+            } else {
+                html! {
+                    .button-bar {
+                        a .button href=(LogTemplateHandler::url(date)) {
+                            "Log a Template"
+                        }
+                    }
+                    (search_form(&LogNewHandler::url(date), &q))
+                    (table_content)
+                }
+            };
 
-    let html_page = page("Add Food Entry", content);
-    Ok((StatusCode::OK, Html(html_page.into_string())))
+            let html_page = page("Add Food Entry", content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+/// Render foods that have been logged before, each with a button to log it
+/// again the way it was last logged.
+fn render_shortcuts(
+    date: Date,
+    eaten_at: Time,
+    slot_id: Option<SlotId>,
+    foods: &[LoggedFood],
+) -> Markup {
+    html! {
+        table {
+            thead {
+                tr {
+                    th {
+                        "Name"
+                    }
+                    th {
+                        "Brand"
+                    }
+                    th {
+                        "Last Amount"
+                    }
+                    th {}
+                }
+            }
+            tbody {
+                @for food in foods {
+                    @let unit_name: &str = match &food.serving_name {
+                        Some(serving_name) => serving_name,
+                        None => food.serving_unit.as_str(),
+                    };
+                    tr {
+                        td {
+                            a href=(LogNewHandler::url_with_food_id(date, food.food_id)) {
+                                (food.name)
+                            }
+                        }
+                        td {
+                            @if food.brand.is_empty() {
+                                "—"
+                            } @else {
+                                (food.brand)
+                            }
+                        }
+                        td {
+                            (format!("{} {}", food.amount, unit_name))
+                        }
+                        td .center {
+                            form method="POST" action=(LogNewHandler::url_with_food_id(date, food.food_id)) {
+                                input type="hidden" name="food_id" value=(food.food_id);
+                                input type="hidden" name="serving_id" value=(food.serving_id.map(|id| id.to_string()).unwrap_or_default());
+                                input type="hidden" name="amount" value=(food.amount);
+                                input type="hidden" name="eaten_at" value=(eaten_at);
+                                input type="hidden" name="slot_id" value=(slot_id.map(|id| id.to_string()).unwrap_or_default());
+                                input .button type="submit" value="Log Again";
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
 
 async fn get_handler_with_food_id(
     State(state): State<ServerState>,
     Path((date, food_id)): Path<(String, FoodId)>,
 ) -> Fallible<(StatusCode, Html<String>)> {
-    let db = state.db.try_lock()?;
+    state
+        .db
+        .run(move |db| {
+            let slots = db.list_meal_slots()?;
+            let date = Date::try_from(date)?;
+            let eaten_at = default_eaten_at(date);
+            let default_slot: Option<SlotId> = MealSlot::default_for(&slots, eaten_at);
+            let form = LogFoodForm {
+                food_id,
+                serving_id: "".to_string(),
+                amount: "".to_string(),
+                eaten_at: eaten_at.to_string(),
+                slot_id: default_slot.map(|id| id.to_string()).unwrap_or_default(),
+            };
+            let html = render_form(db, date, &form, &FieldErrors::new())?;
+            Ok((StatusCode::OK, html))
+        })
+        .await
+}
+
+/// Render the form, filled in with the given values and errors.
+fn render_form(
+    db: &Db,
+    date: Date,
+    form: &LogFoodForm,
+    errors: &FieldErrors,
+) -> Fallible<Html<String>> {
+    let food_id = form.food_id;
     let food = db.get_food(food_id)?;
     let servings = db.list_servings(food_id)?;
-    let date = Date::try_from(date)?;
+    let slots = db.list_meal_slots()?;
 
     let food_title = if food.brand.is_empty() {
         food.name.clone()
@@ -144,13 +300,40 @@ async fn get_handler_with_food_id(
             input type="hidden" name="food_id" value={(food_id.to_string())};
             .form-group {
                 label .label-required for="amount" { "Amount" }
-                input .narrow type="number" id="amount" name="amount" step="0.1" placeholder="e.g., 1.5";
+                input .narrow type="number" id="amount" name="amount" step="0.1" placeholder="e.g., 1.5" value=(form.amount);
+                (field_error(errors, "amount"))
             }
             .form-group {
                 label .label-required for="serving_id" { "Unit" }
                 select .narrow id="serving_id" name="serving_id" {
                     @for (value, label) in options {
-                        option value=(value) { (label) }
+                        @if value == form.serving_id {
+                            option value=(value) selected { (label) }
+                        } @else {
+                            option value=(value) { (label) }
+                        }
+                    }
+                }
+            }
+            .form-group {
+                label .label-required for="eaten_at" { "Time Eaten" }
+                input .narrow type="time" id="eaten_at" name="eaten_at" value=(form.eaten_at);
+                (field_error(errors, "eaten_at"))
+            }
+            .form-group {
+                label for="slot_id" { "Meal" }
+                select .narrow id="slot_id" name="slot_id" {
+                    @for slot in &slots {
+                        @if slot.slot_id.to_string() == form.slot_id {
+                            option value=(slot.slot_id) selected { (slot.name) }
+                        } @else {
+                            option value=(slot.slot_id) { (slot.name) }
+                        }
+                    }
+                    @if form.slot_id.is_empty() {
+                        option value="" selected { "None" }
+                    } @else {
+                        option value="" { "None" }
                     }
                 }
             }
@@ -163,39 +346,75 @@ async fn get_handler_with_food_id(
     let content = form_content;
 
     let html_page = page(&format!("Log {}", food_title), content);
-    Ok((StatusCode::OK, Html(html_page.into_string())))
+    Ok(Html(html_page.into_string()))
 }
 
 #[derive(Deserialize)]
 struct LogFoodForm {
     food_id: FoodId,
     serving_id: String,
-    amount: f64,
+    amount: String,
+    eaten_at: String,
+    slot_id: String,
 }
 
 async fn post_handler(
     State(state): State<ServerState>,
     Path((date, _food_id)): Path<(String, FoodId)>,
     Form(form): Form<LogFoodForm>,
-) -> Fallible<Redirect> {
+) -> Fallible<Response> {
     let date = Date::try_from(date)?;
 
-    let serving_id = if form.serving_id.is_empty() {
-        None
-    } else {
-        Some(form.serving_id.parse::<ServingId>()?)
+    let mut errors = FieldErrors::new();
+    let amount: f64 = errors.positive("amount", &form.amount);
+    let eaten_at: Option<Time> = match Time::try_from(form.eaten_at.clone()) {
+        Ok(time) => Some(time),
+        Err(_) => {
+            errors.add("eaten_at", "Must be a time.");
+            None
+        }
     };
+    state
+        .db
+        .run(move |db| {
+            let (Some(eaten_at), true) = (eaten_at, errors.is_empty()) else {
+                let html = render_form(db, date, &form, &errors)?;
+                return Ok((StatusCode::UNPROCESSABLE_ENTITY, html).into_response());
+            };
 
-    let input = CreateEntryInput {
-        date,
-        food_id: form.food_id,
-        serving_id,
-        amount: form.amount,
-        created_at: Utc::now(),
-    };
+            let serving_id = if form.serving_id.is_empty() {
+                None
+            } else {
+                Some(form.serving_id.parse::<ServingId>()?)
+            };
+
+            let input = CreateEntryInput {
+                date,
+                food_id: form.food_id,
+                serving_id,
+                amount,
+                eaten_at,
+                slot_id: if form.slot_id.is_empty() {
+                    None
+                } else {
+                    Some(form.slot_id.parse::<SlotId>()?)
+                },
+                created_at: Utc::now(),
+            };
 
-    let db = state.db.try_lock()?;
-    db.create_entry(input)?;
+            db.create_entry(input)?;
 
-    Ok(Redirect::to(&LogViewHandler::url(date)))
+            Ok(Redirect::to(&LogViewHandler::url(date)).into_response())
+        })
+        .await
+}
+
+/// When logging food for today, assume it was just eaten. For other days,
+/// there's no way to know, so default to midday.
+pub fn default_eaten_at(date: Date) -> Time {
+    if date == Date::today() {
+        Time::now()
+    } else {
+        Time::from_hm(12, 0).unwrap_or_else(Time::now)
+    }
 }
@@ -0,0 +1,66 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::response::Redirect;
+use axum::routing::post;
+use serde::Deserialize;
+
+use crate::db::ProfileId;
+use crate::error::Fallible;
+use crate::routes::log_view::LogViewHandler;
+use crate::types::Date;
+use crate::www::ServerState;
+
+/// Chooses the target profile for a day.
+pub struct LogProfileHandler {}
+
+impl LogProfileHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route("/log/{date}/profile", post(post_handler))
+    }
+
+    pub fn url(date: Date) -> String {
+        format!("/log/{date}/profile")
+    }
+}
+
+#[derive(Deserialize)]
+struct ChooseProfileForm {
+    /// Empty to go back to the weekday rules.
+    profile_id: String,
+}
+
+async fn post_handler(
+    State(state): State<ServerState>,
+    Path(date): Path<String>,
+    Form(form): Form<ChooseProfileForm>,
+) -> Fallible<Redirect> {
+    let date = Date::try_from(date)?;
+    let profile_id = if form.profile_id.is_empty() {
+        None
+    } else {
+        Some(form.profile_id.parse::<ProfileId>()?)
+    };
+    state
+        .db
+        .run(move |db| {
+            db.set_profile_override(date, profile_id)?;
+            Ok(Redirect::to(&LogViewHandler::url(date)))
+        })
+        .await
+}
@@ -0,0 +1,237 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::response::IntoResponse;
+use axum::response::Redirect;
+use axum::response::Response;
+use axum::routing::get;
+use axum::routing::post;
+use chrono::Utc;
+use maud::html;
+use serde::Deserialize;
+
+use crate::db::CreateQuickEntryInput;
+use crate::db::Db;
+use crate::db::MealSlot;
+use crate::db::SlotId;
+use crate::error::Fallible;
+use crate::routes::log_new::default_eaten_at;
+use crate::routes::log_view::LogViewHandler;
+use crate::types::Date;
+use crate::types::EnergyUnit;
+use crate::types::Nutrition;
+use crate::types::Time;
+use crate::ui::*;
+use crate::validation::FieldErrors;
+use crate::www::ServerState;
+
+/// Logs an estimate of what was eaten, without a food from the library.
+pub struct LogQuickAddHandler {}
+
+impl LogQuickAddHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route("/log/{date}/new/quick", get(get_handler));
+        router.route("/log/{date}/new/quick", post(post_handler))
+    }
+
+    pub fn url(date: Date) -> String {
+        format!("/log/{date}/new/quick")
+    }
+}
+
+async fn get_handler(
+    State(state): State<ServerState>,
+    Path(date): Path<String>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    let date = Date::try_from(date)?;
+    let energy_unit: EnergyUnit = state.energy_unit;
+    state
+        .db
+        .run(move |db| {
+            let slots = db.list_meal_slots()?;
+            let eaten_at = default_eaten_at(date);
+            let default_slot: Option<SlotId> = MealSlot::default_for(&slots, eaten_at);
+            let form = QuickAddForm {
+                eaten_at: eaten_at.to_string(),
+                slot_id: default_slot.map(|id| id.to_string()).unwrap_or_default(),
+                energy_unit: energy_unit.as_str().to_string(),
+                ..QuickAddForm::default()
+            };
+            let html = render_form(db, date, &form, &FieldErrors::new(), energy_unit)?;
+            Ok((StatusCode::OK, html))
+        })
+        .await
+}
+
+/// Render the form, filled in with the given values and errors.
+fn render_form(
+    db: &Db,
+    date: Date,
+    form: &QuickAddForm,
+    errors: &FieldErrors,
+    energy_unit: EnergyUnit,
+) -> Fallible<Html<String>> {
+    let slots = db.list_meal_slots()?;
+    let energy_unit = EnergyUnit::try_from(form.energy_unit.as_str()).unwrap_or(energy_unit);
+
+    let form_content = html! {
+        p {
+            "Record an estimate for food that isn't in the library, e.g. a meal out. Only the energy is required, blank values count as zero."
+        }
+        form .main-form method="post" action=(LogQuickAddHandler::url(date)) {
+            .form-section {
+                .form-section-title {
+                    "Entry"
+                }
+                (form_row(html! {
+                    (form_group(html! {
+                        (label_required("label", "Description"))
+                        (text_input_value("label", "label", &form.label, "e.g., Burger and chips at the pub"))
+                        (field_error(errors, "label"))
+                    }))
+                }))
+                (form_row(html! {
+                    (form_group_half(html! {
+                        (label_required("eaten_at", "Time Eaten"))
+                        input type="time" id="eaten_at" name="eaten_at" value=(form.eaten_at);
+                        (field_error(errors, "eaten_at"))
+                    }))
+                    (form_group_half(html! {
+                        (label("slot_id", "Meal"))
+                        select id="slot_id" name="slot_id" {
+                            @for slot in &slots {
+                                @if slot.slot_id.to_string() == form.slot_id {
+                                    option value=(slot.slot_id) selected { (slot.name) }
+                                } @else {
+                                    option value=(slot.slot_id) { (slot.name) }
+                                }
+                            }
+                            @if form.slot_id.is_empty() {
+                                option value="" selected { "None" }
+                            } @else {
+                                option value="" { "None" }
+                            }
+                        }
+                    }))
+                }))
+            }
+            .form-section {
+                .form-section-title {
+                    "Nutrition Information"
+                }
+                (nutrition_table(html! {
+                    (energy_row("Energy", &form.energy, energy_unit))
+                    (field_error(errors, "energy"))
+                    (nutrition_row_checked("Protein", "protein", "g", &form.protein, 0, errors))
+                    (nutrition_row_checked("Fat, Total", "fat", "g", &form.fat, 0, errors))
+                    (nutrition_row_checked("Saturated", "fat_saturated", "g", &form.fat_saturated, 1, errors))
+                    (nutrition_row_checked("Carbohydrate", "carbs", "g", &form.carbs, 0, errors))
+                    (nutrition_row_checked("Sugars", "carbs_sugars", "g", &form.carbs_sugars, 1, errors))
+                    (nutrition_row_checked("Dietary Fibre", "fibre", "g", &form.fibre, 0, errors))
+                    (nutrition_row_checked("Sodium", "sodium", "mg", &form.sodium, 0, errors))
+                }))
+            }
+            .button-bar {
+                input .button type="submit" value="Log Entry";
+                a .button href=(LogViewHandler::url(date)) {
+                    "Cancel"
+                }
+            }
+        }
+    };
+
+    let html_page = page("Quick Add", form_content);
+    Ok(Html(html_page.into_string()))
+}
+
+#[derive(Deserialize, Default)]
+struct QuickAddForm {
+    label: String,
+    eaten_at: String,
+    slot_id: String,
+    energy: String,
+    energy_unit: String,
+    protein: String,
+    fat: String,
+    fat_saturated: String,
+    carbs: String,
+    carbs_sugars: String,
+    fibre: String,
+    sodium: String,
+}
+
+async fn post_handler(
+    State(state): State<ServerState>,
+    Path(date): Path<String>,
+    Form(form): Form<QuickAddForm>,
+) -> Fallible<Response> {
+    let date = Date::try_from(date)?;
+    let energy_unit: EnergyUnit = state.energy_unit;
+
+    let mut errors = FieldErrors::new();
+    errors.required("label", &form.label);
+    let eaten_at: Option<Time> = match Time::try_from(form.eaten_at.clone()) {
+        Ok(time) => Some(time),
+        Err(_) => {
+            errors.add("eaten_at", "Must be a time.");
+            None
+        }
+    };
+    let nutrition = Nutrition {
+        energy: EnergyUnit::try_from(form.energy_unit.as_ref())?
+            .to_kcal(errors.non_negative("energy", &form.energy)),
+        protein: errors.optional_non_negative("protein", &form.protein),
+        fat: errors.optional_non_negative("fat", &form.fat),
+        fat_saturated: errors.optional_non_negative("fat_saturated", &form.fat_saturated),
+        carbs: errors.optional_non_negative("carbs", &form.carbs),
+        carbs_sugars: errors.optional_non_negative("carbs_sugars", &form.carbs_sugars),
+        fibre: errors.optional_non_negative("fibre", &form.fibre),
+        sodium: errors.optional_non_negative("sodium", &form.sodium),
+    };
+    if nutrition.fat_saturated > nutrition.fat {
+        errors.add("fat_saturated", "Saturated fat can't exceed total fat.");
+    }
+    if nutrition.carbs_sugars > nutrition.carbs {
+        errors.add("carbs_sugars", "Sugars can't exceed total carbohydrate.");
+    }
+    state
+        .db
+        .run(move |db| {
+            let (Some(eaten_at), true) = (eaten_at, errors.is_empty()) else {
+                let html = render_form(db, date, &form, &errors, energy_unit)?;
+                return Ok((StatusCode::UNPROCESSABLE_ENTITY, html).into_response());
+            };
+            let slot_id = if form.slot_id.is_empty() {
+                None
+            } else {
+                Some(form.slot_id.parse::<SlotId>()?)
+            };
+            db.create_quick_entry(CreateQuickEntryInput {
+                date,
+                label: form.label.trim().to_string(),
+                nutrition,
+                eaten_at,
+                slot_id,
+                created_at: Utc::now(),
+            })?;
+            Ok(Redirect::to(&LogViewHandler::url(date)).into_response())
+        })
+        .await
+}
@@ -0,0 +1,207 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::response::IntoResponse;
+use axum::response::Redirect;
+use axum::response::Response;
+use axum::routing::get;
+use axum::routing::post;
+use chrono::Utc;
+use maud::html;
+use serde::Deserialize;
+
+use crate::db::ConvertQuickEntryInput;
+use crate::db::Entry;
+use crate::db::EntryId;
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::routes::food_view::FoodViewHandler;
+use crate::routes::log_view::LogViewHandler;
+use crate::types::BasicUnit;
+use crate::types::Date;
+use crate::ui::*;
+use crate::validation::FieldErrors;
+use crate::www::ServerState;
+
+/// Turns a quick-add entry into an entry of a new library food.
+pub struct LogQuickConvertHandler {}
+
+impl LogQuickConvertHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route("/log/{date}/entry/{entry_id}/convert", get(get_handler));
+        router.route("/log/{date}/entry/{entry_id}/convert", post(post_handler))
+    }
+
+    pub fn url(date: Date, entry_id: EntryId) -> String {
+        format!("/log/{date}/entry/{entry_id}/convert")
+    }
+}
+
+async fn get_handler(
+    State(state): State<ServerState>,
+    Path((date, entry_id)): Path<(String, EntryId)>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    let date = Date::try_from(date)?;
+    state
+        .db
+        .run(move |db| {
+            let entry: Entry = db.get_entry(entry_id)?;
+            let Some(label) = entry.label else {
+                return Err(AppError::bad_request("This entry is already of a food."));
+            };
+            let form = ConvertForm {
+                food_name: label,
+                brand: "".to_string(),
+                serving_unit: BasicUnit::Grams.as_str().to_string(),
+                serving_name: "serving".to_string(),
+                serving_amount: "".to_string(),
+            };
+            Ok((
+                StatusCode::OK,
+                render_form(date, entry_id, &form, &FieldErrors::new()),
+            ))
+        })
+        .await
+}
+
+/// Render the form, filled in with the given values and errors.
+fn render_form(
+    date: Date,
+    entry_id: EntryId,
+    form: &ConvertForm,
+    errors: &FieldErrors,
+) -> Html<String> {
+    let form_content = html! {
+        p {
+            "The entry becomes one serving of a new food in the library, so it can be logged again. The food's nutrition is worked out from the entry's and the size of the serving."
+        }
+        form .main-form method="post" action=(LogQuickConvertHandler::url(date, entry_id)) {
+            .form-section {
+                .form-section-title {
+                    "Basic Information"
+                }
+                (form_row(html! {
+                    (form_group(html! {
+                        (label_required("food_name", "Food Name"))
+                        (text_input_value("food_name", "food_name", &form.food_name, "e.g., Pub Burger"))
+                        (field_error(errors, "food_name"))
+                    }))
+                }))
+                (form_row(html! {
+                    (form_group_half(html! {
+                        (label_with_hint("brand", "Brand", "(optional)"))
+                        (text_input_value("brand", "brand", &form.brand, "e.g., The Local"))
+                    }))
+                    (form_group_half(html! {
+                        (label_required("serving_unit", "Base Unit"))
+                        (select_with_selected("serving_unit", "serving_unit", vec![
+                            ("g".to_string(), "Grams (g)".to_string()),
+                            ("ml".to_string(), "Milliliters (ml)".to_string()),
+                        ], &form.serving_unit))
+                    }))
+                }))
+            }
+            .form-section {
+                .form-section-title {
+                    "Serving"
+                }
+                (form_row(html! {
+                    (form_group_half(html! {
+                        (label_required("serving_name", "Serving Name"))
+                        (text_input_value("serving_name", "serving_name", &form.serving_name, "e.g., plate"))
+                        (field_error(errors, "serving_name"))
+                    }))
+                    (form_group_half(html! {
+                        (label_with_hint("serving_amount", "Serving Size", "(in the base unit)"))
+                        (number_input_value("serving_amount", "serving_amount", "0.1", &form.serving_amount))
+                        (field_error(errors, "serving_amount"))
+                    }))
+                }))
+            }
+            .button-bar {
+                input .button type="submit" value="Save as Food";
+                a .button href=(LogViewHandler::url(date)) {
+                    "Cancel"
+                }
+            }
+        }
+    };
+
+    let html_page = page("Save as Food", form_content);
+    Html(html_page.into_string())
+}
+
+#[derive(Deserialize)]
+struct ConvertForm {
+    food_name: String,
+    brand: String,
+    serving_unit: String,
+    serving_name: String,
+    serving_amount: String,
+}
+
+async fn post_handler(
+    State(state): State<ServerState>,
+    Path((date, entry_id)): Path<(String, EntryId)>,
+    Form(form): Form<ConvertForm>,
+) -> Fallible<Response> {
+    let date = Date::try_from(date)?;
+    let serving_unit = BasicUnit::try_from(form.serving_unit.as_ref())?;
+
+    let mut errors = FieldErrors::new();
+    errors.required("food_name", &form.food_name);
+    errors.required("serving_name", &form.serving_name);
+    let serving_amount: f64 = errors.positive("serving_amount", &form.serving_amount);
+    state
+        .db
+        .run(move |db| {
+            if errors.is_empty() {
+                // The serving has to be big enough to hold the macronutrients
+                // of the entry.
+                let entry: Entry = db.get_entry(entry_id)?;
+                let mut nutrition_errors = FieldErrors::new();
+                entry
+                    .food_nutrition
+                    .scale(100.0 / serving_amount)
+                    .validate(serving_unit, &mut nutrition_errors);
+                if !nutrition_errors.is_empty() {
+                    errors.add(
+                        "serving_amount",
+                        "Too small for the nutrition of this entry.",
+                    );
+                }
+            }
+            if !errors.is_empty() {
+                let html = render_form(date, entry_id, &form, &errors);
+                return Ok((StatusCode::UNPROCESSABLE_ENTITY, html).into_response());
+            }
+            let food_id = db.convert_quick_entry(ConvertQuickEntryInput {
+                entry_id,
+                name: form.food_name.trim().to_string(),
+                brand: form.brand.trim().to_string(),
+                serving_unit,
+                serving_name: form.serving_name.trim().to_string(),
+                serving_amount,
+                created_at: Utc::now(),
+            })?;
+            Ok(Redirect::to(&FoodViewHandler::url(food_id)).into_response())
+        })
+        .await
+}
@@ -0,0 +1,195 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::response::IntoResponse;
+use axum::response::Redirect;
+use axum::response::Response;
+use axum::routing::get;
+use axum::routing::post;
+use chrono::Utc;
+use maud::html;
+
+use crate::db::CreateMealTemplateInput;
+use crate::db::Db;
+use crate::db::Entry;
+use crate::db::EntryId;
+use crate::db::LogEntry;
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::routes::form_fields::FormFields;
+use crate::routes::log_view::LogViewHandler;
+use crate::routes::template_view::TemplateViewHandler;
+use crate::types::Date;
+use crate::ui::*;
+use crate::validation::FieldErrors;
+use crate::www::ServerState;
+
+/// Saves entries from the log as a meal template.
+pub struct LogSaveTemplateHandler {}
+
+impl LogSaveTemplateHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route("/log/{date}/save-template", get(get_handler));
+        router.route("/log/{date}/save-template", post(post_handler))
+    }
+
+    pub fn url(date: Date) -> String {
+        format!("/log/{date}/save-template")
+    }
+}
+
+async fn get_handler(
+    State(state): State<ServerState>,
+    Path(date): Path<String>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    let date = Date::try_from(date)?;
+    state
+        .db
+        .run(move |db| {
+            let html = render_form(db, date, "", &[], &FieldErrors::new())?;
+            Ok((StatusCode::OK, html))
+        })
+        .await
+}
+
+/// Render the form, filled in with the given name, checked entries and
+/// errors.
+fn render_form(
+    db: &Db,
+    date: Date,
+    name: &str,
+    checked: &[EntryId],
+    errors: &FieldErrors,
+) -> Fallible<Html<String>> {
+    let entries: Vec<LogEntry> = db.list_log_entries(date, date)?;
+
+    let content = if entries.is_empty() {
+        html! {
+            p {
+                "No food logged for this date."
+            }
+            .button-bar {
+                a .button href=(LogViewHandler::url(date)) {
+                    "Back"
+                }
+            }
+        }
+    } else {
+        html! {
+            form method="post" action=(LogSaveTemplateHandler::url(date)) {
+                .form-group {
+                    (label_required("name", "Name"))
+                    (text_input_value("name", "name", name, "e.g., Weekday Breakfast"))
+                    (field_error(errors, "name"))
+                }
+                (field_error(errors, "entry_id"))
+                table {
+                    thead {
+                        tr {
+                            th {}
+                            th { "Time" }
+                            th { "Food" }
+                            th { "Brand" }
+                            th { "Amount" }
+                        }
+                    }
+                    tbody {
+                        @for log_entry in &entries {
+                            tr {
+                                td .center {
+                                    input type="checkbox" name="entry_id" value=(log_entry.entry.entry_id) aria-label="Include" checked[checked.contains(&log_entry.entry.entry_id)] disabled[log_entry.is_quick_add()];
+                                }
+                                td .center {
+                                    (log_entry.entry.eaten_at)
+                                }
+                                td {
+                                    (log_entry.food_name)
+                                }
+                                td {
+                                    @if log_entry.food_brand.is_empty() {
+                                        "—"
+                                    } @else {
+                                        (log_entry.food_brand)
+                                    }
+                                }
+                                td {
+                                    (log_entry.describe_amount())
+                                }
+                            }
+                        }
+                    }
+                }
+                .button-bar {
+                    input .button type="submit" value="Save Template";
+                    a .button href=(LogViewHandler::url(date)) {
+                        "Cancel"
+                    }
+                }
+            }
+        }
+    };
+
+    let html_page = page("Save as Template", content);
+    Ok(Html(html_page.into_string()))
+}
+
+/// Create the template from the checked entries.
+async fn post_handler(
+    State(state): State<ServerState>,
+    Path(date): Path<String>,
+    Form(fields): Form<FormFields>,
+) -> Fallible<Response> {
+    let date = Date::try_from(date)?;
+    let name: String = fields.get("name").unwrap_or_default().trim().to_string();
+    let entry_ids: Vec<EntryId> = fields.entry_ids()?;
+    let mut errors = FieldErrors::new();
+    errors.required("name", &name);
+    state
+        .db
+        .run(move |db| {
+            let mut foods: usize = 0;
+            for entry_id in &entry_ids {
+                let entry: Entry = db.get_entry(*entry_id)?;
+                if entry.date != date {
+                    return Err(AppError::bad_request(
+                        "An entry is from a different day than the log.",
+                    ));
+                }
+                if entry.food_id.is_some() {
+                    foods += 1;
+                }
+            }
+            // Quick-add entries have no food, so they can't become lines.
+            if foods == 0 {
+                errors.add("entry_id", "Select at least one entry of a food.");
+            }
+            if !errors.is_empty() {
+                let html = render_form(db, date, &name, &entry_ids, &errors)?;
+                return Ok((StatusCode::UNPROCESSABLE_ENTITY, html).into_response());
+            }
+            let input = CreateMealTemplateInput {
+                name,
+                created_at: Utc::now(),
+            };
+            let template_id = db.create_meal_template_from_entries(input, &entry_ids)?;
+            Ok(Redirect::to(&TemplateViewHandler::url(template_id)).into_response())
+        })
+        .await
+}
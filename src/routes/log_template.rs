@@ -0,0 +1,174 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::response::Redirect;
+use axum::routing::get;
+use axum::routing::post;
+use chrono::Utc;
+use maud::html;
+use serde::Deserialize;
+
+use crate::db::LogMealTemplateInput;
+use crate::db::MealSlot;
+use crate::db::MealTemplate;
+use crate::db::SlotId;
+use crate::db::TemplateId;
+use crate::db::TemplateLine;
+use crate::error::Fallible;
+use crate::routes::log_new::LogNewHandler;
+use crate::routes::log_new::default_eaten_at;
+use crate::routes::log_view::LogViewHandler;
+use crate::routes::template_list::TemplateListHandler;
+use crate::types::Date;
+use crate::types::Time;
+use crate::ui::*;
+use crate::validation::check_positive;
+use crate::www::ServerState;
+
+/// Logs every food in a meal template.
+pub struct LogTemplateHandler {}
+
+impl LogTemplateHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route("/log/{date}/new/template", get(get_handler));
+        router.route("/log/{date}/new/template/{template_id}", post(post_handler))
+    }
+
+    pub fn url(date: Date) -> String {
+        format!("/log/{date}/new/template")
+    }
+
+    pub fn url_with_template_id(date: Date, template_id: TemplateId) -> String {
+        format!("/log/{date}/new/template/{template_id}")
+    }
+}
+
+async fn get_handler(
+    State(state): State<ServerState>,
+    Path(date): Path<String>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    let date = Date::try_from(date)?;
+    state
+        .db
+        .run(move |db| {
+            let slots: Vec<MealSlot> = db.list_meal_slots()?;
+            let eaten_at: Time = default_eaten_at(date);
+            let slot_id: Option<SlotId> = MealSlot::default_for(&slots, eaten_at);
+            let mut templates: Vec<(MealTemplate, Vec<TemplateLine>)> = Vec::new();
+            for template in db.list_meal_templates()? {
+                let lines = db.list_template_lines(template.template_id)?;
+                templates.push((template, lines));
+            }
+
+            let content = html! {
+                @if templates.is_empty() {
+                    p {
+                        "No templates. "
+                        a href=(TemplateListHandler::url()) {
+                            "Create one."
+                        }
+                    }
+                } @else {
+                    p {
+                        "Each food is logged with its amount multiplied by the factor, e.g. 0.5 for half the meal."
+                    }
+                    table {
+                        thead {
+                            tr {
+                                th { "Template" }
+                                th { "Foods" }
+                                th { "Factor" }
+                            }
+                        }
+                        tbody {
+                            @for (template, lines) in &templates {
+                                @let factor_id = format!("factor_{}", template.template_id);
+                                tr {
+                                    td {
+                                        (template.name)
+                                    }
+                                    td {
+                                        @for (i, line) in lines.iter().enumerate() {
+                                            @if i > 0 {
+                                                ", "
+                                            }
+                                            (line.name)
+                                        }
+                                    }
+                                    td .center {
+                                        form .row-actions method="post" action=(LogTemplateHandler::url_with_template_id(date, template.template_id)) {
+                                            input type="hidden" name="eaten_at" value=(eaten_at);
+                                            input type="hidden" name="slot_id" value=(slot_id.map(|id| id.to_string()).unwrap_or_default());
+                                            input .narrow type="number" id=(factor_id) name="factor" step="0.05" min="0" value="1" aria-label="Factor";
+                                            input .button type="submit" value="Log" disabled[lines.is_empty()];
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                .button-bar {
+                    a .button href=(LogNewHandler::url(date)) {
+                        "Cancel"
+                    }
+                }
+            };
+
+            let html_page = page("Log Template", content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+#[derive(Deserialize)]
+struct LogTemplateForm {
+    factor: f64,
+    eaten_at: String,
+    slot_id: String,
+}
+
+async fn post_handler(
+    State(state): State<ServerState>,
+    Path((date, template_id)): Path<(String, TemplateId)>,
+    Form(form): Form<LogTemplateForm>,
+) -> Fallible<Redirect> {
+    let date = Date::try_from(date)?;
+    let slot_id = if form.slot_id.is_empty() {
+        None
+    } else {
+        Some(form.slot_id.parse::<SlotId>()?)
+    };
+    let input = LogMealTemplateInput {
+        template_id,
+        date,
+        factor: check_positive("Factor", form.factor)?,
+        eaten_at: Time::try_from(form.eaten_at)?,
+        slot_id,
+        created_at: Utc::now(),
+    };
+    state
+        .db
+        .run(move |db| {
+            db.log_meal_template(input)?;
+            Ok(Redirect::to(&LogViewHandler::url(date)))
+        })
+        .await
+}
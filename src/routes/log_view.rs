@@ -18,18 +18,30 @@ use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::Html;
 use axum::routing::get;
-use chrono::Local;
 use maud::Markup;
 use maud::html;
 
-use crate::db::Db;
+use crate::db::DayProfile;
 use crate::db::Entry;
-use crate::db::FoodEntry;
+use crate::db::LogEntry;
+use crate::db::MealSlot;
+use crate::db::Target;
+use crate::db::TargetProfile;
 use crate::error::Fallible;
 use crate::routes::food_view::FoodViewHandler;
+use crate::routes::log_copy::LogCopyHandler;
 use crate::routes::log_delete::LogDeleteHandler;
+use crate::routes::log_edit::LogEditHandler;
+use crate::routes::log_move::LogMoveHandler;
 use crate::routes::log_new::LogNewHandler;
+use crate::routes::log_profile::LogProfileHandler;
+use crate::routes::log_quick_add::LogQuickAddHandler;
+use crate::routes::log_quick_convert::LogQuickConvertHandler;
+use crate::routes::log_save_template::LogSaveTemplateHandler;
+use crate::routes::week_view::WeekViewHandler;
 use crate::types::Date;
+use crate::types::EnergyUnit;
+use crate::types::Nutrient;
 use crate::types::Nutrition;
 use crate::ui::*;
 use crate::www::ServerState;
@@ -51,47 +63,74 @@ async fn handler(
     Path(date): Path<String>,
 ) -> Fallible<(StatusCode, Html<String>)> {
     let date: Date = Date::try_from(date)?;
-    let db = state.db.try_lock()?;
-    let entries: Vec<Entry> = db.list_entries(date)?;
-    let tbl = render_log_table(&db, &entries, date)?;
-    let totals: Nutrition = calculate_totals(&db, &entries)?;
-    let totals: Markup = render_totals(totals);
-    let content = html! {
-        .button-bar {
-            a .button href=(LogViewHandler::url(date.prev_day())) {
-                "← Previous"
-            }
-            a .button href=(LogViewHandler::url(Date::today())) {
-                "Today"
-            }
-            a .button href=(LogViewHandler::url(date.next_day())) {
-                "Next →"
-            }
-            .spacer {}
-            a .button href=(LogNewHandler::url(date)) {
-                "Log Food"
-            }
-        }
-        (tbl)
-        h2 {
-            "Totals"
-        }
-        (totals)
-    };
-    let title = format!("Log: {}", date.humanize());
-    let html_page = page(&title, content);
-    Ok((StatusCode::OK, Html(html_page.into_string())))
+    let energy_unit: EnergyUnit = state.energy_unit;
+    state
+        .db
+        .run(move |db| {
+            let entries: Vec<LogEntry> = db.list_log_entries(date, date)?;
+            let slots: Vec<MealSlot> = db.list_meal_slots()?;
+            let tbl = render_log_table(&entries, &slots, date, energy_unit);
+            let totals: Nutrition = calculate_totals(&entries);
+            let day_profile: DayProfile = db.get_day_profile(date)?;
+            let profiles: Vec<TargetProfile> = db.list_target_profiles()?;
+            let targets: Vec<Target> = db.get_targets(day_profile.profile.profile_id, date)?;
+            let totals: Markup = render_totals(totals, &targets, energy_unit);
+            let content = html! {
+                .button-bar {
+                    a .button href=(LogViewHandler::url(date.prev_day())) {
+                        "← Previous"
+                    }
+                    a .button href=(LogViewHandler::url(Date::today())) {
+                        "Today"
+                    }
+                    a .button href=(LogViewHandler::url(date.next_day())) {
+                        "Next →"
+                    }
+                    a .button href=(WeekViewHandler::url(date)) {
+                        "Week"
+                    }
+                    .spacer {}
+                    a .button href=(LogCopyHandler::url(date)) {
+                        "Copy From…"
+                    }
+                    a .button href=(LogSaveTemplateHandler::url(date)) {
+                        "Save as Template"
+                    }
+                    a .button href=(LogQuickAddHandler::url(date)) {
+                        "Quick Add"
+                    }
+                    a .button href=(LogNewHandler::url(date)) {
+                        "Log Food"
+                    }
+                }
+                (tbl)
+                h2 {
+                    "Totals"
+                }
+                (render_profile_picker(date, &day_profile, &profiles))
+                (totals)
+            };
+            let title = format!("Log: {}", date.humanize());
+            let html_page = page(&title, content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
 }
 
-fn render_log_table(db: &Db, entries: &[Entry], date: Date) -> Fallible<Markup> {
+fn render_log_table(
+    entries: &[LogEntry],
+    slots: &[MealSlot],
+    date: Date,
+    energy_unit: EnergyUnit,
+) -> Markup {
     if entries.is_empty() {
-        Ok(html! {
+        html! {
             p {
                 "No food logged for this date."
             }
-        })
+        }
     } else {
-        Ok(html! {
+        html! {
             table {
                 thead {
                     tr {
@@ -108,7 +147,7 @@ fn render_log_table(db: &Db, entries: &[Entry], date: Date) -> Fallible<Markup>
                             "Amount"
                         }
                         th .numeric {
-                            "Energy (kcal)"
+                            "Energy (" (energy_unit.as_str()) ")"
                         }
                         th .numeric {
                             "Protein (g)"
@@ -134,32 +173,84 @@ fn render_log_table(db: &Db, entries: &[Entry], date: Date) -> Fallible<Markup>
                     }
                 }
                 tbody {
-                    @for entry in entries {
-                        (render_log_entry_row(db, entry, date)?)
+                    @for slot in slots {
+                        (render_meal(&slot.name, entries.iter().filter(|e| e.entry.slot_id == Some(slot.slot_id)).collect(), slots, date, energy_unit))
                     }
+                    (render_meal("Other", entries.iter().filter(|e| e.entry.slot_id.is_none()).collect(), slots, date, energy_unit))
                 }
             }
-        })
+        }
+    }
+}
+
+/// Render the entries of a meal, followed by their subtotal. Renders nothing
+/// for meals without entries.
+fn render_meal(
+    name: &str,
+    entries: Vec<&LogEntry>,
+    slots: &[MealSlot],
+    date: Date,
+    energy_unit: EnergyUnit,
+) -> Markup {
+    if entries.is_empty() {
+        return html! {};
+    }
+    let subtotal: Nutrition = calculate_totals(entries.iter().copied());
+    html! {
+        tr .meal-header {
+            td colspan="12" {
+                (name)
+            }
+        }
+        @for entry in &entries {
+            (render_log_entry_row(entry, slots, date, energy_unit))
+        }
+        tr .subtotal {
+            td colspan="4" {
+                "Subtotal"
+            }
+            td .numeric {
+                (format!("{:.0}", energy_unit.convert_kcal(subtotal.energy)))
+            }
+            td .numeric {
+                (format!("{:.1}", subtotal.protein))
+            }
+            td .numeric {
+                (format!("{:.1}", subtotal.fat))
+            }
+            td .numeric {
+                (format!("{:.1}", subtotal.fat_saturated))
+            }
+            td .numeric {
+                (format!("{:.1}", subtotal.carbs))
+            }
+            td .numeric {
+                (format!("{:.1}", subtotal.fibre))
+            }
+            td .numeric {
+                (format!("{:.0}", subtotal.sodium))
+            }
+            td {}
+        }
     }
 }
 
-fn render_log_entry_row(db: &Db, entry: &Entry, date: Date) -> Fallible<Markup> {
-    let food: FoodEntry = db.get_food(entry.food_id)?;
-    let nutrition: Nutrition = entry.nutrition(db)?;
+fn render_log_entry_row(
+    log_entry: &LogEntry,
+    slots: &[MealSlot],
+    date: Date,
+    energy_unit: EnergyUnit,
+) -> Markup {
+    let entry: &Entry = &log_entry.entry;
+    let nutrition: Nutrition = log_entry.nutrition;
     // If there's a custom unit, use that. Otherwise, use the base unit name.
-    let unit_name: String = if let Some(serving_id) = entry.serving_id {
-        let serving = db.get_serving_by_id(serving_id)?;
-        serving.serving_name
-    } else {
-        food.serving_unit.as_str().to_string()
+    let amount_str: String = match (&log_entry.serving_name, log_entry.serving_unit) {
+        (Some(serving_name), _) => format!("{:.0} {}", entry.amount, serving_name),
+        (None, Some(unit)) => format!("{:.0} {}", entry.amount, unit.as_str()),
+        (None, None) => "—".to_string(),
     };
-    let time_str: String = entry
-        .created_at
-        .with_timezone(&Local)
-        .format("%H:%M")
-        .to_string();
-    let amount_str = format!("{:.0} {}", entry.amount, unit_name);
-    let energy_str = format!("{:.0}", nutrition.energy);
+    let time_str: String = entry.eaten_at.to_string();
+    let energy_str = format!("{:.0}", energy_unit.convert_kcal(nutrition.energy));
     let protein_str = format!("{:.1}", nutrition.protein);
     let fat_str = format!("{:.1}", nutrition.fat);
     let fat_saturated_str = format!("{:.1}", nutrition.fat_saturated);
@@ -167,21 +258,29 @@ fn render_log_entry_row(db: &Db, entry: &Entry, date: Date) -> Fallible<Markup>
     let fibre_str = format!("{:.1}", nutrition.fibre);
     let sodium_str = format!("{:.0}", nutrition.sodium);
 
-    Ok(html! {
+    html! {
         tr {
             td .center {
                 (time_str)
             }
             td {
-                a href=(FoodViewHandler::url(food.food_id)) {
-                    (food.name)
+                @if let Some(food_id) = entry.food_id {
+                    a href=(FoodViewHandler::url(food_id)) {
+                        (log_entry.food_name)
+                    }
+                } @else {
+                    (log_entry.food_name)
+                    " "
+                    span .label-hint {
+                        "(quick add)"
+                    }
                 }
             }
             td {
-                @if food.brand.is_empty() {
+                @if log_entry.food_brand.is_empty() {
                     "—"
                 } @else {
-                    (food.brand)
+                    (log_entry.food_brand)
                 }
             }
             td {
@@ -209,111 +308,124 @@ fn render_log_entry_row(db: &Db, entry: &Entry, date: Date) -> Fallible<Markup>
                 (sodium_str)
             }
             td .center {
-                form method="POST" action=(LogDeleteHandler::url(date, entry.entry_id)) {
-                    input .button type="submit" value="Delete";
+                .row-actions {
+                    form .row-actions method="POST" action=(LogMoveHandler::url(date, entry.entry_id)) {
+                        select name="slot_id" {
+                            @for slot in slots {
+                                @if entry.slot_id == Some(slot.slot_id) {
+                                    option value=(slot.slot_id) selected { (slot.name) }
+                                } @else {
+                                    option value=(slot.slot_id) { (slot.name) }
+                                }
+                            }
+                            @if entry.slot_id.is_none() {
+                                option value="" selected { "Other" }
+                            } @else {
+                                option value="" { "Other" }
+                            }
+                        }
+                        input .button type="submit" value="Move";
+                    }
+                    @if log_entry.is_quick_add() {
+                        a .button href=(LogQuickConvertHandler::url(date, entry.entry_id)) {
+                            "Save as Food"
+                        }
+                    } @else {
+                        a .button href=(LogEditHandler::url(date, entry.entry_id)) {
+                            "Edit"
+                        }
+                    }
+                    form method="POST" action=(LogDeleteHandler::url(date, entry.entry_id)) {
+                        input .button type="submit" value="Delete";
+                    }
                 }
             }
         }
-    })
+    }
 }
 
-fn calculate_totals(db: &Db, entries: &[Entry]) -> Fallible<Nutrition> {
-    let mut n: Nutrition = Nutrition {
-        energy: 0.0,
-        protein: 0.0,
-        fat: 0.0,
-        fat_saturated: 0.0,
-        carbs: 0.0,
-        carbs_sugars: 0.0,
-        fibre: 0.0,
-        sodium: 0.0,
-    };
+fn calculate_totals<'a>(entries: impl IntoIterator<Item = &'a LogEntry>) -> Nutrition {
+    let mut n: Nutrition = Nutrition::zero();
     for entry in entries {
-        let en: Nutrition = entry.nutrition(db)?;
-        n = n + en;
+        n = n + entry.nutrition;
     }
-    Ok(n)
+    n
 }
 
-fn humanize_float(f: f64) -> String {
-    format!("{:.1}", f)
+/// Show which target profile applies, and let the user choose another one for
+/// this day.
+fn render_profile_picker(
+    date: Date,
+    day_profile: &DayProfile,
+    profiles: &[TargetProfile],
+) -> Markup {
+    let mut options: Vec<(String, String)> = vec![("".to_string(), "Weekly schedule".to_string())];
+    for profile in profiles {
+        options.push((profile.profile_id.to_string(), profile.name.clone()));
+    }
+    let selected = if day_profile.overridden {
+        day_profile.profile.profile_id.to_string()
+    } else {
+        "".to_string()
+    };
+    html! {
+        form .profile-picker method="post" action=(LogProfileHandler::url(date)) {
+            "Targets: "
+            strong { (day_profile.profile.name) }
+            @if !day_profile.overridden {
+                " (from the weekly schedule)"
+            }
+            (select_with_selected("profile_id", "profile_id", options, &selected))
+            input .button type="submit" value="Switch";
+        }
+    }
 }
 
-fn render_totals(t: Nutrition) -> Markup {
-    let Nutrition {
-        energy,
-        protein,
-        fat,
-        fat_saturated,
-        carbs,
-        carbs_sugars,
-        fibre,
-        sodium,
-    } = t;
+fn render_totals(t: Nutrition, targets: &[Target], energy_unit: EnergyUnit) -> Markup {
     html! {
         table .totals {
-            tr {
-                th {
-                    "Energy"
-                }
-                td {
-                    (humanize_float(energy))
+            thead {
+                tr {
+                    th { "Nutrient" }
+                    th { "Total" }
+                    th { "Target" }
+                    th { "Remaining" }
+                    th { "% of Target" }
                 }
             }
-            tr {
-                th {
-                    "Protein"
-                }
-                td {
-                    (humanize_float(protein))
-                }
-            }
-            tr {
-                th {
-                    "Fat"
-                }
-                td {
-                    (humanize_float(fat))
-                }
-            }
-            tr {
-                th {
-                    "Fat — Saturated"
-                }
-                td {
-                    (humanize_float(fat_saturated))
-                }
-            }
-            tr {
-                th {
-                    "Carbs"
-                }
-                td {
-                    (humanize_float(carbs))
-                }
-            }
-            tr {
-                th {
-                    "Carbs — Sugars"
-                }
-                td {
-                    (humanize_float(carbs_sugars))
-                }
-            }
-            tr {
-                th {
-                    "Fibre"
-                }
-                td {
-                    (humanize_float(fibre))
-                }
-            }
-            tr {
-                th {
-                    "Sodium"
-                }
-                td {
-                    (humanize_float(sodium))
+            tbody {
+                @for nutrient in Nutrient::ALL {
+                    @let total = t.get(nutrient);
+                    @let unit = nutrient.display_unit(energy_unit);
+                    @match targets.iter().find(|target| target.nutrient == nutrient) {
+                        Some(target) => {
+                            // Progress is measured against the upper bound when
+                            // there is one, since that's the limit to stay under.
+                            @let goal = target.max.or(target.min).unwrap_or(0.0);
+                            tr class=(target.status(total).as_str()) {
+                                th { (nutrient.label()) }
+                                td .numeric { (humanize_float(nutrient.display_value(total, energy_unit))) " " (unit) }
+                                td .numeric { (target.describe(energy_unit)) }
+                                td .numeric { (humanize_float(nutrient.display_value(goal - total, energy_unit))) " " (unit) }
+                                td .numeric {
+                                    @if goal > 0.0 {
+                                        (format!("{:.0}%", total / goal * 100.0))
+                                    } @else {
+                                        "—"
+                                    }
+                                }
+                            }
+                        }
+                        None => {
+                            tr {
+                                th { (nutrient.label()) }
+                                td .numeric { (humanize_float(nutrient.display_value(total, energy_unit))) " " (unit) }
+                                td .numeric { "—" }
+                                td .numeric { "—" }
+                                td .numeric { "—" }
+                            }
+                        }
+                    }
                 }
             }
         }
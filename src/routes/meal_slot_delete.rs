@@ -0,0 +1,49 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::response::Redirect;
+use axum::routing::post;
+
+use crate::db::SlotId;
+use crate::error::Fallible;
+use crate::routes::meal_slot_list::MealSlotListHandler;
+use crate::www::ServerState;
+
+pub struct MealSlotDeleteHandler {}
+
+impl MealSlotDeleteHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route("/meals/{slot_id}/delete", post(handler))
+    }
+
+    pub fn url(slot_id: SlotId) -> String {
+        format!("/meals/{slot_id}/delete")
+    }
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Path(slot_id): Path<SlotId>,
+) -> Fallible<Redirect> {
+    state
+        .db
+        .run(move |db| {
+            db.delete_meal_slot(slot_id)?;
+            Ok(Redirect::to(MealSlotListHandler::url()))
+        })
+        .await
+}
@@ -0,0 +1,101 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::routing::get;
+use maud::html;
+
+use crate::db::MealSlot;
+use crate::error::Fallible;
+use crate::routes::meal_slot_delete::MealSlotDeleteHandler;
+use crate::routes::meal_slot_new::MealSlotNewHandler;
+use crate::ui::*;
+use crate::www::ServerState;
+
+pub struct MealSlotListHandler {}
+
+impl MealSlotListHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route(Self::url(), get(handler))
+    }
+
+    pub fn url() -> &'static str {
+        "/meals"
+    }
+}
+
+async fn handler(State(state): State<ServerState>) -> Fallible<(StatusCode, Html<String>)> {
+    state
+        .db
+        .run(move |db| {
+            let slots: Vec<MealSlot> = db.list_meal_slots()?;
+
+            let content = html! {
+                p {
+                    "Entries in the daily log are grouped by meal. When logging food, the meal defaults to the one that started most recently."
+                }
+                table {
+                    thead {
+                        tr {
+                            th { "Name" }
+                            th { "Starts At" }
+                            th { "Delete" }
+                        }
+                    }
+                    tbody {
+                        @for slot in &slots {
+                            tr {
+                                td {
+                                    (slot.name)
+                                }
+                                td .center {
+                                    @match slot.starts_at {
+                                        Some(time) => (time.to_string()),
+                                        None => "—",
+                                    }
+                                }
+                                td .center {
+                                    form method="post" action=(MealSlotDeleteHandler::url(slot.slot_id)) {
+                                        input .button type="submit" value="Delete";
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                h2 {
+                    "Add Meal"
+                }
+                form method="post" action=(MealSlotNewHandler::url()) {
+                    .form-group {
+                        (label("name", "Name"))
+                        (text_input("name", "name", "e.g., Second Breakfast"))
+                    }
+                    .form-group {
+                        (label_with_hint("starts_at", "Starts At", "(optional, leave blank if this meal is never the default)"))
+                        input type="time" id="starts_at" name="starts_at";
+                    }
+                    input .button type="submit" value="Add Meal";
+                }
+            };
+
+            let html_page = page("Meals", content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
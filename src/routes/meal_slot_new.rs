@@ -0,0 +1,68 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::State;
+use axum::response::Redirect;
+use axum::routing::post;
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::db::CreateMealSlotInput;
+use crate::error::Fallible;
+use crate::routes::meal_slot_list::MealSlotListHandler;
+use crate::types::Time;
+use crate::www::ServerState;
+
+pub struct MealSlotNewHandler {}
+
+impl MealSlotNewHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route(Self::url(), post(handler))
+    }
+
+    pub fn url() -> &'static str {
+        "/meals/new"
+    }
+}
+
+#[derive(Deserialize)]
+struct CreateMealSlotForm {
+    name: String,
+    starts_at: String,
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Form(form): Form<CreateMealSlotForm>,
+) -> Fallible<Redirect> {
+    let CreateMealSlotForm { name, starts_at } = form;
+    let input = CreateMealSlotInput {
+        name,
+        starts_at: if starts_at.is_empty() {
+            None
+        } else {
+            Some(Time::try_from(starts_at)?)
+        },
+        created_at: Utc::now(),
+    };
+    state
+        .db
+        .run(move |db| {
+            db.create_meal_slot(input)?;
+            Ok(Redirect::to(MealSlotListHandler::url()))
+        })
+        .await
+}
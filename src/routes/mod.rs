@@ -13,13 +13,46 @@
 // limitations under the License.
 
 pub mod assets;
+pub mod calendar_view;
+pub mod food_archive;
+pub mod food_delete;
 pub mod food_edit;
+pub mod food_form;
 pub mod food_list;
 pub mod food_new;
 pub mod food_view;
+pub mod form_fields;
+pub mod ingredient_delete;
+pub mod ingredient_new;
+pub mod log_copy;
 pub mod log_delete;
+pub mod log_edit;
+pub mod log_move;
 pub mod log_new;
+pub mod log_profile;
+pub mod log_quick_add;
+pub mod log_quick_convert;
+pub mod log_save_template;
+pub mod log_template;
 pub mod log_view;
+pub mod meal_slot_delete;
+pub mod meal_slot_list;
+pub mod meal_slot_new;
+pub mod recipe_edit;
+pub mod recipe_new;
+pub mod report_view;
 pub mod root;
 pub mod serving_delete;
 pub mod serving_new;
+pub mod target_edit;
+pub mod target_profile_delete;
+pub mod target_profile_list;
+pub mod target_profile_new;
+pub mod target_weekdays;
+pub mod template_delete;
+pub mod template_line_delete;
+pub mod template_line_new;
+pub mod template_list;
+pub mod template_new;
+pub mod template_view;
+pub mod week_view;
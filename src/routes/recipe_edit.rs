@@ -0,0 +1,154 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::response::Redirect;
+use axum::routing::get;
+use axum::routing::post;
+use maud::html;
+use serde::Deserialize;
+
+use crate::db::EditRecipeInput;
+use crate::db::FoodEntry;
+use crate::db::FoodId;
+use crate::db::Recipe;
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::routes::food_view::FoodViewHandler;
+use crate::types::BasicUnit;
+use crate::types::Date;
+use crate::ui::*;
+use crate::validation::check_positive;
+use crate::www::ServerState;
+
+pub struct RecipeEditHandler {}
+
+impl RecipeEditHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route("/library/{food_id}/recipe/edit", get(get_handler));
+        router.route("/library/{food_id}/recipe/edit", post(post_handler))
+    }
+
+    pub fn url(food_id: FoodId) -> String {
+        format!("/library/{food_id}/recipe/edit")
+    }
+}
+
+async fn get_handler(
+    State(state): State<ServerState>,
+    Path(food_id): Path<FoodId>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    state
+        .db
+        .run(move |db| {
+            let food: FoodEntry = db.get_food(food_id)?;
+            let recipe: Recipe = db
+                .get_recipe(food_id)?
+                .ok_or_else(|| AppError::not_found("This food is not a recipe."))?;
+
+            let form_content = html! {
+                form .main-form method="post" action=(RecipeEditHandler::url(food_id)) {
+                    (form_section("Basic Information", html! {
+                        (form_row(html! {
+                            (form_group(html! {
+                                (label_required("food_name", "Recipe Name"))
+                                (text_input_value("food_name", "food_name", &food.name, "e.g., Lentil Soup"))
+                            }))
+                        }))
+                        (form_row(html! {
+                            (form_group_half(html! {
+                                (label_with_hint("brand", "Brand", "(optional)"))
+                                (text_input_value("brand", "brand", &food.brand, "e.g., Homemade"))
+                            }))
+                            (form_group_half(html! {
+                                (label_required("serving_unit", "Base Unit"))
+                                (select_with_selected("serving_unit", "serving_unit", vec![
+                                    ("g".to_string(), "Grams (g)".to_string()),
+                                    ("ml".to_string(), "Milliliters (ml)".to_string()),
+                                ], food.serving_unit.as_str()))
+                            }))
+                        }))
+                        (form_row(html! {
+                            (form_group_half(html! {
+                                (label_with_hint("cooked_weight", "Total Cooked Weight", "(in the base unit)"))
+                                (number_input_value("cooked_weight", "cooked_weight", "any", &recipe.cooked_weight.to_string()))
+                            }))
+                        }))
+                    }))
+                    (form_section("Past Entries", html! {
+                        (form_row(html! {
+                            (form_group_half(html! {
+                                (label_with_hint("correct_from", "Apply Correction From", "(optional, leave blank to leave past entries unchanged)"))
+                                (date_input("correct_from", "correct_from"))
+                            }))
+                        }))
+                    }))
+                    .button-bar {
+                        input .button type="submit" value="Save";
+                    }
+                }
+            };
+
+            let html_page = page(&format!("Edit {}", food.name), form_content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+#[derive(Deserialize)]
+struct EditRecipeForm {
+    food_name: String,
+    brand: String,
+    serving_unit: String,
+    cooked_weight: f64,
+    correct_from: String,
+}
+
+async fn post_handler(
+    State(state): State<ServerState>,
+    Path(food_id): Path<FoodId>,
+    Form(form): Form<EditRecipeForm>,
+) -> Fallible<Redirect> {
+    let EditRecipeForm {
+        food_name,
+        brand,
+        serving_unit,
+        cooked_weight,
+        correct_from,
+    } = form;
+    let input = EditRecipeInput {
+        food_id,
+        name: food_name,
+        brand,
+        serving_unit: BasicUnit::try_from(serving_unit.as_ref())?,
+        cooked_weight: check_positive("Cooked weight", cooked_weight)?,
+        correct_from: if correct_from.is_empty() {
+            None
+        } else {
+            Some(Date::try_from(correct_from)?)
+        },
+    };
+    state
+        .db
+        .run(move |db| {
+            db.edit_recipe(input)?;
+            Ok(Redirect::to(&FoodViewHandler::url(food_id)))
+        })
+        .await
+}
@@ -0,0 +1,121 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::response::Redirect;
+use axum::routing::get;
+use axum::routing::post;
+use chrono::Utc;
+use maud::html;
+use serde::Deserialize;
+
+use crate::db::CreateRecipeInput;
+use crate::db::FoodId;
+use crate::error::Fallible;
+use crate::routes::food_view::FoodViewHandler;
+use crate::types::BasicUnit;
+use crate::ui::*;
+use crate::validation::check_positive;
+use crate::www::ServerState;
+
+pub struct RecipeNewHandler {}
+
+impl RecipeNewHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route(Self::url(), get(get_handler));
+        router.route(Self::url(), post(post_handler))
+    }
+
+    pub fn url() -> &'static str {
+        "/library/new/recipe"
+    }
+}
+
+async fn get_handler() -> Fallible<(StatusCode, Html<String>)> {
+    let form_content = html! {
+        form .main-form method="post" action=(RecipeNewHandler::url()) {
+            (form_section("Basic Information", html! {
+                (form_row(html! {
+                    (form_group(html! {
+                        (label_required("food_name", "Recipe Name"))
+                        (text_input("food_name", "food_name", "e.g., Lentil Soup"))
+                    }))
+                }))
+                (form_row(html! {
+                    (form_group_half(html! {
+                        (label_with_hint("brand", "Brand", "(optional)"))
+                        (text_input("brand", "brand", "e.g., Homemade"))
+                    }))
+                    (form_group_half(html! {
+                        (label_required("serving_unit", "Base Unit"))
+                        (select("serving_unit", "serving_unit", vec![
+                            ("g".to_string(), "Grams (g)".to_string()),
+                            ("ml".to_string(), "Milliliters (ml)".to_string()),
+                        ]))
+                    }))
+                }))
+                (form_row(html! {
+                    (form_group_half(html! {
+                        (label_with_hint("cooked_weight", "Total Cooked Weight", "(in the base unit)"))
+                        (number_input("cooked_weight", "cooked_weight", "0.1", "e.g., 1500"))
+                    }))
+                }))
+            }))
+            .button-bar {
+                input .button type="submit" value="Save";
+            }
+        }
+    };
+
+    let html_page = page("Add New Recipe", form_content);
+    Ok((StatusCode::OK, Html(html_page.into_string())))
+}
+
+#[derive(Deserialize)]
+struct CreateRecipeForm {
+    food_name: String,
+    brand: String,
+    serving_unit: String,
+    cooked_weight: f64,
+}
+
+async fn post_handler(
+    State(state): State<ServerState>,
+    Form(form): Form<CreateRecipeForm>,
+) -> Fallible<Redirect> {
+    let CreateRecipeForm {
+        food_name,
+        brand,
+        serving_unit,
+        cooked_weight,
+    } = form;
+    let input = CreateRecipeInput {
+        name: food_name,
+        brand,
+        serving_unit: BasicUnit::try_from(serving_unit.as_ref())?,
+        cooked_weight: check_positive("Cooked weight", cooked_weight)?,
+        created_at: Utc::now(),
+    };
+    state
+        .db
+        .run(move |db| {
+            let food_id: FoodId = db.create_recipe(input)?;
+            Ok(Redirect::to(&FoodViewHandler::url(food_id)))
+        })
+        .await
+}
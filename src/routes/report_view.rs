@@ -0,0 +1,172 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use axum::Router;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::routing::get;
+use maud::html;
+use serde::Deserialize;
+
+use crate::chart::Band;
+use crate::chart::ChartDay;
+use crate::chart::ChartKind;
+use crate::chart::render_chart;
+use crate::chart::rolling_average;
+use crate::db::DailyTotals;
+use crate::db::Target;
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::types::Date;
+use crate::types::EnergyUnit;
+use crate::types::Nutrient;
+use crate::ui::*;
+use crate::www::ServerState;
+
+/// How many days the report covers by default.
+const DEFAULT_REPORT_DAYS: i64 = 30;
+
+/// The longest range a report can cover.
+const MAX_REPORT_DAYS: i64 = 366;
+
+/// How many days the rolling average is taken over.
+const AVERAGE_WINDOW_DAYS: i64 = 7;
+
+/// Charts a nutrient's daily totals over a range of dates.
+pub struct ReportViewHandler {}
+
+impl ReportViewHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route("/reports", get(handler))
+    }
+
+    pub fn url() -> String {
+        "/reports".to_string()
+    }
+}
+
+#[derive(Deserialize)]
+struct ReportQuery {
+    nutrient: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    kind: Option<String>,
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Query(query): Query<ReportQuery>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    // Empty fields fall back to their defaults.
+    let field = |value: Option<String>| value.filter(|v| !v.is_empty());
+    let nutrient: Nutrient = match field(query.nutrient) {
+        Some(nutrient) => Nutrient::try_from(nutrient.as_str())?,
+        None => Nutrient::Energy,
+    };
+    let kind: ChartKind = match field(query.kind) {
+        Some(kind) => ChartKind::try_from(kind.as_str())?,
+        None => ChartKind::Line,
+    };
+    let to: Date = match field(query.to) {
+        Some(to) => Date::try_from(to)?,
+        None => Date::today(),
+    };
+    let from: Date = match field(query.from) {
+        Some(from) => Date::try_from(from)?,
+        None => to.add_days(1 - DEFAULT_REPORT_DAYS),
+    };
+    if from > to {
+        return Err(AppError::bad_request(
+            "The start date is after the end date.",
+        ));
+    }
+    let days: i64 = from.days_until(to) + 1;
+    if days > MAX_REPORT_DAYS {
+        return Err(AppError::bad_request(format!(
+            "Reports can cover at most {MAX_REPORT_DAYS} days."
+        )));
+    }
+    let energy_unit: EnergyUnit = state.energy_unit;
+    state
+        .db
+        .run(move |db| {
+            // Start early enough that the first day has a full window to
+            // average over.
+            let lead = AVERAGE_WINDOW_DAYS - 1;
+            let totals: Vec<DailyTotals> = db.list_daily_totals(from.add_days(-lead), to)?;
+            let totals: HashMap<Date, &DailyTotals> =
+                totals.iter().map(|day| (day.date, day)).collect();
+            let targets: HashMap<Date, Vec<Target>> = db.list_day_targets(from, to)?;
+            let values: Vec<Option<f64>> = (-lead..days)
+                .map(|offset| {
+                    let date = from.add_days(offset);
+                    totals.get(&date).map(|day| day.nutrition.get(nutrient))
+                })
+                .collect();
+            let averages = rolling_average(&values, AVERAGE_WINDOW_DAYS as usize);
+            let display = |v: Option<f64>| v.map(|v| nutrient.display_value(v, energy_unit));
+            let mut chart_days: Vec<ChartDay> = Vec::new();
+            let days_in_range = values.into_iter().zip(averages).enumerate().skip(lead as usize);
+            for (index, (value, average)) in days_in_range {
+                let date = from.add_days(index as i64 - lead);
+                let target: Option<&Target> = targets
+                    .get(&date)
+                    .and_then(|targets| targets.iter().find(|target| target.nutrient == nutrient));
+                chart_days.push(ChartDay {
+                    date,
+                    value: display(value),
+                    average: display(average),
+                    band: target.map(|target| Band {
+                        min: display(target.min),
+                        max: display(target.max),
+                    }),
+                    // Compared before conversion, since targets are stored in
+                    // the same units as totals.
+                    status: target
+                        .zip(value)
+                        .map(|(target, value)| target.status(value)),
+                });
+            }
+            let nutrient_options: Vec<(String, String)> = Nutrient::ALL
+                .into_iter()
+                .map(|n| (n.as_str().to_string(), n.label().to_string()))
+                .collect();
+            let kind_options: Vec<(String, String)> = ChartKind::ALL
+                .into_iter()
+                .map(|k| (k.as_str().to_string(), k.label().to_string()))
+                .collect();
+            let content = html! {
+                form .search-form method="get" action=(ReportViewHandler::url()) {
+                    (select_with_selected("nutrient", "nutrient", nutrient_options, nutrient.as_str()))
+                    (label("from", "From"))
+                    (date_input_value("from", "from", &from.to_string()))
+                    (label("to", "To"))
+                    (date_input_value("to", "to", &to.to_string()))
+                    (select_with_selected("kind", "kind", kind_options, kind.as_str()))
+                    input .button type="submit" value="Show";
+                }
+                h2 {
+                    (nutrient.label()) ": " (from.humanize()) " – " (to.humanize())
+                }
+                (render_chart(kind, &chart_days, nutrient.display_unit(energy_unit)))
+            };
+            let html_page = page("Reports", content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
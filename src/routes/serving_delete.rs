@@ -15,22 +15,34 @@
 use axum::Router;
 use axum::extract::Path;
 use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
 use axum::response::Redirect;
+use axum::routing::get;
 use axum::routing::post;
+use maud::html;
 
+use crate::db::FoodEntry;
 use crate::db::FoodId;
+use crate::db::Serving;
 use crate::db::ServingId;
+use crate::db::ServingUsage;
 use crate::error::Fallible;
 use crate::routes::food_view::FoodViewHandler;
+use crate::ui::*;
 use crate::www::ServerState;
 
 pub struct ServingDeleteHandler {}
 
 impl ServingDeleteHandler {
     pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route(
+            "/library/{food_id}/servings/{serving_id}/delete",
+            get(get_handler),
+        );
         router.route(
             "/library/{food_id}/servings/{serving_id}/delete",
-            post(handler),
+            post(post_handler),
         )
     }
 
@@ -39,11 +51,61 @@ impl ServingDeleteHandler {
     }
 }
 
-async fn handler(
+async fn get_handler(
+    State(state): State<ServerState>,
+    Path((food_id, serving_id)): Path<(FoodId, ServingId)>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    state
+        .db
+        .run(move |db| {
+            let food: FoodEntry = db.get_food(food_id)?;
+            let serving: Serving = db.get_serving(food_id, serving_id)?;
+            let usage: ServingUsage = db.get_serving_usage(serving_id)?;
+            let unit = food.serving_unit.as_str();
+
+            let content = html! {
+                p {
+                    (format!("\"{}\" is {} {}.", serving.serving_name, serving.serving_amount, unit))
+                }
+                @if usage.entries == 0 && usage.ingredients == 0 && usage.template_lines == 0 {
+                    p {
+                        "No log entries, recipes or meal templates use this serving."
+                    }
+                } @else {
+                    p {
+                        (format!(
+                            "{} log entries, {} recipe ingredients and {} meal template lines use this serving. They will be converted to amounts in {}, so their totals won't change.",
+                            usage.entries, usage.ingredients, usage.template_lines, unit
+                        ))
+                    }
+                }
+                form .button-bar method="post" action=(ServingDeleteHandler::url(food_id, serving_id)) {
+                    input .button type="submit" value="Delete Serving";
+                    a .button href=(FoodViewHandler::url(food_id)) {
+                        "Cancel"
+                    }
+                }
+            };
+
+            let html_page = page(
+                &format!("Delete Serving: {}", serving.serving_name),
+                content,
+            );
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+async fn post_handler(
     State(state): State<ServerState>,
     Path((food_id, serving_id)): Path<(FoodId, ServingId)>,
 ) -> Fallible<Redirect> {
-    let db = state.db.try_lock()?;
-    db.delete_serving(serving_id)?;
-    Ok(Redirect::to(&FoodViewHandler::url(food_id)))
+    state
+        .db
+        .run(move |db| {
+            db.get_serving(food_id, serving_id)?;
+            db.delete_serving(serving_id)?;
+            Ok(Redirect::to(&FoodViewHandler::url(food_id)))
+        })
+        .await
 }
@@ -25,6 +25,7 @@ use crate::db::FoodId;
 use crate::db::ServingInput;
 use crate::error::Fallible;
 use crate::routes::food_view::FoodViewHandler;
+use crate::validation::check_positive;
 use crate::www::ServerState;
 
 pub struct ServingNewHandler {}
@@ -58,10 +59,14 @@ async fn handler(
     let input = ServingInput {
         food_id,
         serving_name,
-        serving_amount,
+        serving_amount: check_positive("Serving amount", serving_amount)?,
         created_at,
     };
-    let db = state.db.try_lock()?;
-    db.create_serving(input)?;
-    Ok(Redirect::to(&FoodViewHandler::url(food_id)))
+    state
+        .db
+        .run(move |db| {
+            db.create_serving(input)?;
+            Ok(Redirect::to(&FoodViewHandler::url(food_id)))
+        })
+        .await
 }
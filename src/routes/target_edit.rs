@@ -0,0 +1,275 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::response::IntoResponse;
+use axum::response::Redirect;
+use axum::response::Response;
+use axum::routing::get;
+use axum::routing::post;
+use chrono::Utc;
+use maud::html;
+
+use crate::db::Db;
+use crate::db::ProfileId;
+use crate::db::SetTargetInput;
+use crate::db::Target;
+use crate::db::TargetProfile;
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::types::Date;
+use crate::types::EnergyUnit;
+use crate::types::Nutrient;
+use crate::ui::*;
+use crate::validation::FieldErrors;
+use crate::www::ServerState;
+
+pub struct TargetEditHandler {}
+
+impl TargetEditHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route("/targets/{profile_id}", get(get_handler));
+        router.route("/targets/{profile_id}", post(post_handler))
+    }
+
+    pub fn url(profile_id: ProfileId) -> String {
+        format!("/targets/{profile_id}")
+    }
+}
+
+async fn get_handler(
+    State(state): State<ServerState>,
+    Path(profile_id): Path<ProfileId>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    let energy_unit: EnergyUnit = state.energy_unit;
+    state
+        .db
+        .run(move |db| {
+            let profile: TargetProfile = get_profile(db, profile_id)?;
+            let today = Date::today();
+            let current: Vec<Target> = db.get_targets(profile_id, today)?;
+            let history: Vec<Target> = db.list_targets(profile_id)?;
+
+            let mut form: HashMap<String, String> = HashMap::new();
+            for target in &current {
+                let nutrient = target.nutrient;
+                let value = |v: f64| nutrient.display_value(v, energy_unit).to_string();
+                if let Some(min) = target.min {
+                    form.insert(format!("{}_min", nutrient.as_str()), value(min));
+                }
+                if let Some(max) = target.max {
+                    form.insert(format!("{}_max", nutrient.as_str()), value(max));
+                }
+            }
+            form.insert("effective_from".to_string(), today.to_string());
+
+            let html = render_form(&profile, &history, &form, &FieldErrors::new(), energy_unit);
+            Ok((StatusCode::OK, html))
+        })
+        .await
+}
+
+/// Render the form, filled in with the given values and errors.
+fn render_form(
+    profile: &TargetProfile,
+    history: &[Target],
+    form: &HashMap<String, String>,
+    errors: &FieldErrors,
+    energy_unit: EnergyUnit,
+) -> Html<String> {
+    let value = |name: &str| form.get(name).cloned().unwrap_or_default();
+    let content = html! {
+        form .main-form method="post" action=(TargetEditHandler::url(profile.profile_id)) {
+            (form_section("Daily Targets", html! {
+                table {
+                    thead {
+                        tr {
+                            th { "Nutrient" }
+                            th { "Minimum" }
+                            th { "Maximum" }
+                        }
+                    }
+                    tbody {
+                        @for nutrient in Nutrient::ALL {
+                            @let min_name = format!("{}_min", nutrient.as_str());
+                            @let max_name = format!("{}_max", nutrient.as_str());
+                            tr {
+                                td {
+                                    (nutrient.label()) " (" (nutrient.display_unit(energy_unit)) ")"
+                                }
+                                td .center {
+                                    input type="number" name=(min_name) step="any" min="0" value=(value(&min_name));
+                                    (field_error(errors, &min_name))
+                                }
+                                td .center {
+                                    input type="number" name=(max_name) step="any" min="0" value=(value(&max_name));
+                                    (field_error(errors, &max_name))
+                                }
+                            }
+                        }
+                    }
+                }
+            }))
+            (form_row(html! {
+                (form_group_half(html! {
+                    (label_with_hint("effective_from", "Effective From", "(days before this keep their old targets)"))
+                    input type="date" id="effective_from" name="effective_from" value=(value("effective_from"));
+                    (field_error(errors, "effective_from"))
+                }))
+            }))
+            .button-bar {
+                input .button type="submit" value="Save";
+            }
+        }
+        h2 {
+            "History"
+        }
+        @if history.is_empty() {
+            p {
+                "No targets set."
+            }
+        } @else {
+            table {
+                thead {
+                    tr {
+                        th { "Effective From" }
+                        th { "Nutrient" }
+                        th { "Target" }
+                    }
+                }
+                tbody {
+                    @for target in history {
+                        tr {
+                            td .center {
+                                (target.effective_from.to_string())
+                            }
+                            td {
+                                (target.nutrient.label())
+                            }
+                            td .numeric {
+                                @if target.min.is_none() && target.max.is_none() {
+                                    "Removed"
+                                } @else {
+                                    (target.describe(energy_unit))
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    let title = format!("Targets: {}", profile.name);
+    let html_page = page(&title, content);
+    Html(html_page.into_string())
+}
+
+async fn post_handler(
+    State(state): State<ServerState>,
+    Path(profile_id): Path<ProfileId>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Fallible<Response> {
+    let energy_unit: EnergyUnit = state.energy_unit;
+    // Check every bound before saving any, so a bad value doesn't leave the
+    // targets half-updated.
+    let mut errors = FieldErrors::new();
+    let effective_from: String = form.get("effective_from").cloned().unwrap_or_default();
+    let effective_from: Option<Date> = match Date::try_from(effective_from) {
+        Ok(date) => Some(date),
+        Err(_) => {
+            errors.add("effective_from", "Must be a date.");
+            None
+        }
+    };
+    let mut bounds: Vec<(Nutrient, Option<f64>, Option<f64>)> = Vec::new();
+    for nutrient in Nutrient::ALL {
+        let min_name = format!("{}_min", nutrient.as_str());
+        let max_name = format!("{}_max", nutrient.as_str());
+        let min = parse_bound(&form, &min_name, &mut errors);
+        let max = parse_bound(&form, &max_name, &mut errors);
+        if let (Some(min), Some(max)) = (min, max)
+            && min > max
+        {
+            errors.add(&max_name, "Can't be less than the minimum.");
+        }
+        bounds.push((nutrient, min, max));
+    }
+    state
+        .db
+        .run(move |db| {
+            let profile: TargetProfile = get_profile(db, profile_id)?;
+            let Some(effective_from) = effective_from.filter(|_| errors.is_empty()) else {
+                let history: Vec<Target> = db.list_targets(profile_id)?;
+                let html = render_form(&profile, &history, &form, &errors, energy_unit);
+                return Ok((StatusCode::UNPROCESSABLE_ENTITY, html).into_response());
+            };
+            let current: Vec<Target> = db.get_targets(profile_id, effective_from)?;
+            let created_at = Utc::now();
+            let mut inputs: Vec<SetTargetInput> = Vec::new();
+            for (nutrient, min, max) in bounds {
+                // Only record the nutrients that changed, to keep the history
+                // legible. The bounds are compared in the units they were
+                // entered in, so converting kJ back and forth doesn't count as
+                // a change.
+                let display = |v: Option<f64>| v.map(|v| nutrient.display_value(v, energy_unit));
+                let (old_min, old_max) = match current.iter().find(|t| t.nutrient == nutrient) {
+                    Some(target) => (display(target.min), display(target.max)),
+                    None => (None, None),
+                };
+                if (min, max) != (old_min, old_max) {
+                    let stored = |v: Option<f64>| v.map(|v| nutrient.stored_value(v, energy_unit));
+                    inputs.push(SetTargetInput {
+                        profile_id,
+                        nutrient,
+                        effective_from,
+                        min: stored(min),
+                        max: stored(max),
+                        created_at,
+                    });
+                }
+            }
+            db.set_targets(inputs)?;
+            Ok(Redirect::to(&TargetEditHandler::url(profile_id)).into_response())
+        })
+        .await
+}
+
+fn get_profile(db: &Db, profile_id: ProfileId) -> Fallible<TargetProfile> {
+    db.list_target_profiles()?
+        .into_iter()
+        .find(|p| p.profile_id == profile_id)
+        .ok_or_else(|| AppError::not_found("Target profile not found."))
+}
+
+/// Parse a bound. A blank value means there is no bound.
+fn parse_bound(
+    form: &HashMap<String, String>,
+    name: &str,
+    errors: &mut FieldErrors,
+) -> Option<f64> {
+    let value: &str = form.get(name).map(String::as_str).unwrap_or_default();
+    if value.trim().is_empty() {
+        None
+    } else {
+        Some(errors.optional_non_negative(name, value))
+    }
+}
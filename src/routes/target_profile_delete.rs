@@ -0,0 +1,49 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::response::Redirect;
+use axum::routing::post;
+
+use crate::db::ProfileId;
+use crate::error::Fallible;
+use crate::routes::target_profile_list::TargetProfileListHandler;
+use crate::www::ServerState;
+
+pub struct TargetProfileDeleteHandler {}
+
+impl TargetProfileDeleteHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route("/targets/{profile_id}/delete", post(handler))
+    }
+
+    pub fn url(profile_id: ProfileId) -> String {
+        format!("/targets/{profile_id}/delete")
+    }
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Path(profile_id): Path<ProfileId>,
+) -> Fallible<Redirect> {
+    state
+        .db
+        .run(move |db| {
+            db.delete_target_profile(profile_id)?;
+            Ok(Redirect::to(TargetProfileListHandler::url()))
+        })
+        .await
+}
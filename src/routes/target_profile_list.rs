@@ -0,0 +1,140 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::routing::get;
+use maud::html;
+
+use crate::db::DEFAULT_PROFILE_ID;
+use crate::db::Db;
+use crate::db::ProfileId;
+use crate::db::TargetProfile;
+use crate::error::Fallible;
+use crate::routes::target_edit::TargetEditHandler;
+use crate::routes::target_profile_delete::TargetProfileDeleteHandler;
+use crate::routes::target_profile_new::TargetProfileNewHandler;
+use crate::routes::target_weekdays::TargetWeekdaysHandler;
+use crate::ui::*;
+use crate::validation::FieldErrors;
+use crate::www::ServerState;
+
+pub struct TargetProfileListHandler {}
+
+impl TargetProfileListHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route(Self::url(), get(handler))
+    }
+
+    pub fn url() -> &'static str {
+        "/targets"
+    }
+}
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+async fn handler(State(state): State<ServerState>) -> Fallible<(StatusCode, Html<String>)> {
+    state
+        .db
+        .run(move |db| {
+            let html = render_page(db, "", &FieldErrors::new())?;
+            Ok((StatusCode::OK, html))
+        })
+        .await
+}
+
+/// Render the profile list, with the new-profile form filled in with the
+/// given name and errors.
+pub fn render_page(db: &Db, name: &str, errors: &FieldErrors) -> Fallible<Html<String>> {
+    let profiles: Vec<TargetProfile> = db.list_target_profiles()?;
+    let weekdays: [Option<ProfileId>; 7] = db.list_weekday_profiles()?;
+    let options: Vec<(String, String)> = profiles
+        .iter()
+        .map(|p| (p.profile_id.to_string(), p.name.clone()))
+        .collect();
+
+    let content = html! {
+        p {
+            "Targets are grouped into profiles, e.g. for training and rest days. Each day uses the profile assigned to its weekday, unless a different one is chosen from the log."
+        }
+        table {
+            thead {
+                tr {
+                    th { "Profile" }
+                    th { "Delete" }
+                }
+            }
+            tbody {
+                @for profile in &profiles {
+                    tr {
+                        td {
+                            a href=(TargetEditHandler::url(profile.profile_id)) {
+                                (profile.name)
+                            }
+                        }
+                        td .center {
+                            @if profile.profile_id == DEFAULT_PROFILE_ID {
+                                "—"
+                            } @else {
+                                form method="post" action=(TargetProfileDeleteHandler::url(profile.profile_id)) {
+                                    input .button type="submit" value="Delete";
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        h2 {
+            "Add Profile"
+        }
+        form method="post" action=(TargetProfileNewHandler::url()) {
+            .form-group {
+                (label("name", "Name"))
+                (text_input_value("name", "name", name, "e.g., Training Day"))
+                (field_error(errors, "name"))
+            }
+            input .button type="submit" value="Add Profile";
+        }
+
+        h2 {
+            "Weekly Schedule"
+        }
+        form method="post" action=(TargetWeekdaysHandler::url()) {
+            @for (weekday, name) in WEEKDAYS.iter().enumerate() {
+                @let id = format!("weekday_{weekday}");
+                @let selected = weekdays[weekday].unwrap_or(DEFAULT_PROFILE_ID).to_string();
+                .form-group {
+                    (label(&id, name))
+                    (select_with_selected(&id, &id, options.clone(), &selected))
+                }
+            }
+            input .button type="submit" value="Save Schedule";
+        }
+    };
+
+    let html_page = page("Targets", content);
+    Ok(Html(html_page.into_string()))
+}
@@ -0,0 +1,72 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Redirect;
+use axum::response::Response;
+use axum::routing::post;
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::db::CreateTargetProfileInput;
+use crate::error::Fallible;
+use crate::routes::target_edit::TargetEditHandler;
+use crate::routes::target_profile_list::render_page;
+use crate::validation::FieldErrors;
+use crate::www::ServerState;
+
+pub struct TargetProfileNewHandler {}
+
+impl TargetProfileNewHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route(Self::url(), post(handler))
+    }
+
+    pub fn url() -> &'static str {
+        "/targets/new"
+    }
+}
+
+#[derive(Deserialize)]
+struct CreateTargetProfileForm {
+    name: String,
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Form(form): Form<CreateTargetProfileForm>,
+) -> Fallible<Response> {
+    let name: String = form.name.trim().to_string();
+    let mut errors = FieldErrors::new();
+    errors.required("name", &name);
+    state
+        .db
+        .run(move |db| {
+            if !errors.is_empty() {
+                let html = render_page(db, &name, &errors)?;
+                return Ok((StatusCode::UNPROCESSABLE_ENTITY, html).into_response());
+            }
+            let input = CreateTargetProfileInput {
+                name,
+                created_at: Utc::now(),
+            };
+            let profile_id = db.create_target_profile(input)?;
+            Ok(Redirect::to(&TargetEditHandler::url(profile_id)).into_response())
+        })
+        .await
+}
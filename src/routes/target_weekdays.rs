@@ -0,0 +1,73 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use axum::Form;
+use axum::Router;
+use axum::extract::State;
+use axum::response::Redirect;
+use axum::routing::post;
+
+use crate::db::DEFAULT_PROFILE_ID;
+use crate::db::ProfileId;
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::routes::target_profile_list::TargetProfileListHandler;
+use crate::www::ServerState;
+
+/// Assigns target profiles to the days of the week.
+pub struct TargetWeekdaysHandler {}
+
+impl TargetWeekdaysHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route(Self::url(), post(handler))
+    }
+
+    pub fn url() -> &'static str {
+        "/targets/weekdays"
+    }
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Fallible<Redirect> {
+    // Parse every day before saving any, so a bad value doesn't leave the
+    // schedule half-updated.
+    let mut profiles: [Option<ProfileId>; 7] = [None; 7];
+    for (weekday, profile) in profiles.iter_mut().enumerate() {
+        let profile_id = match form.get(&format!("weekday_{weekday}")) {
+            Some(value) => value.parse::<ProfileId>()?,
+            None => DEFAULT_PROFILE_ID,
+        };
+        // Days on the default profile don't need a rule.
+        *profile = Some(profile_id).filter(|id| *id != DEFAULT_PROFILE_ID);
+    }
+    state
+        .db
+        .run(move |db| {
+            let known: Vec<ProfileId> = db
+                .list_target_profiles()?
+                .into_iter()
+                .map(|p| p.profile_id)
+                .collect();
+            if profiles.iter().flatten().any(|id| !known.contains(id)) {
+                return Err(AppError::bad_request("Unknown target profile."));
+            }
+            db.set_weekday_profiles(&profiles)?;
+            Ok(Redirect::to(TargetProfileListHandler::url()))
+        })
+        .await
+}
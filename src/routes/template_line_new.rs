@@ -0,0 +1,224 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::response::Redirect;
+use axum::routing::get;
+use axum::routing::post;
+use chrono::Utc;
+use maud::html;
+use serde::Deserialize;
+
+use crate::db::CreateTemplateLineInput;
+use crate::db::FoodId;
+use crate::db::ServingId;
+use crate::db::TemplateId;
+use crate::error::Fallible;
+use crate::routes::template_view::TemplateViewHandler;
+use crate::ui::*;
+use crate::validation::check_positive;
+use crate::www::ServerState;
+
+pub struct TemplateLineNewHandler {}
+
+impl TemplateLineNewHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        let router = router.route("/templates/{template_id}/lines/new", get(get_handler));
+        let router = router.route(
+            "/templates/{template_id}/lines/new/food/{food_id}",
+            get(get_handler_with_food_id),
+        );
+        router.route(
+            "/templates/{template_id}/lines/new/food/{food_id}",
+            post(post_handler),
+        )
+    }
+
+    pub fn url(template_id: TemplateId) -> String {
+        format!("/templates/{template_id}/lines/new")
+    }
+
+    pub fn url_with_food_id(template_id: TemplateId, food_id: FoodId) -> String {
+        format!("/templates/{template_id}/lines/new/food/{food_id}")
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: Option<String>,
+}
+
+async fn get_handler(
+    State(state): State<ServerState>,
+    Path(template_id): Path<TemplateId>,
+    Query(query): Query<SearchQuery>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    let q: String = query.q.unwrap_or_default();
+    state
+        .db
+        .run(move |db| {
+            let template = db.get_meal_template(template_id)?;
+            let foods = db.search_foods(&q)?;
+
+            let table_content = if foods.is_empty() {
+                html! {
+                    p {
+                        "No matching foods."
+                    }
+                }
+            } else {
+                html! {
+                    table {
+                        thead {
+                            tr {
+                                th {
+                                    "Name"
+                                }
+                                th {
+                                    "Brand"
+                                }
+                            }
+                        }
+                        tbody {
+                            @for food in &foods {
+                                tr {
+                                    td {
+                                        a href=(TemplateLineNewHandler::url_with_food_id(template_id, food.food_id)) {
+                                            (food.name)
+                                        }
+                                    }
+                                    td {
+                                        @if food.brand.is_empty() {
+                                            "—"
+                                        } @else {
+                                            (food.brand)
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+
+            let content = html! {
+                (search_form(&TemplateLineNewHandler::url(template_id), &q))
+                (table_content)
+            };
+
+            let html_page = page(&format!("Add Food to {}", template.name), content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+async fn get_handler_with_food_id(
+    State(state): State<ServerState>,
+    Path((template_id, food_id)): Path<(TemplateId, FoodId)>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    state
+        .db
+        .run(move |db| {
+            let template = db.get_meal_template(template_id)?;
+            let food = db.get_food(food_id)?;
+            let servings = db.list_servings(food_id)?;
+
+            let food_title = if food.brand.is_empty() {
+                food.name.clone()
+            } else {
+                format!("{} — {}", food.name, food.brand)
+            };
+
+            let mut options: Vec<(String, String)> = Vec::new();
+            for serving in &servings {
+                options.push((
+                    serving.serving_id.to_string(),
+                    format!(
+                        "{} ({} {})",
+                        serving.serving_name,
+                        serving.serving_amount,
+                        food.serving_unit.as_str()
+                    ),
+                ));
+            }
+            options.push(("".to_string(), food.serving_unit.as_str().to_owned()));
+
+            let form_content = html! {
+                form .main-form method="post" action=(TemplateLineNewHandler::url_with_food_id(template_id, food_id)) {
+                    .form-group {
+                        label .label-required for="amount" { "Amount" }
+                        input .narrow type="number" id="amount" name="amount" step="0.1" placeholder="e.g., 250";
+                    }
+                    .form-group {
+                        label .label-required for="serving_id" { "Unit" }
+                        select .narrow id="serving_id" name="serving_id" {
+                            @for (value, label) in options {
+                                option value=(value) { (label) }
+                            }
+                        }
+                    }
+                    .button-bar {
+                        input .button type="submit" value="Add Food";
+                    }
+                }
+            };
+
+            let html_page = page(
+                &format!("Add {} to {}", food_title, template.name),
+                form_content,
+            );
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+#[derive(Deserialize)]
+struct CreateTemplateLineForm {
+    serving_id: String,
+    amount: f64,
+}
+
+async fn post_handler(
+    State(state): State<ServerState>,
+    Path((template_id, food_id)): Path<(TemplateId, FoodId)>,
+    Form(form): Form<CreateTemplateLineForm>,
+) -> Fallible<Redirect> {
+    let serving_id = if form.serving_id.is_empty() {
+        None
+    } else {
+        Some(form.serving_id.parse::<ServingId>()?)
+    };
+
+    let input = CreateTemplateLineInput {
+        template_id,
+        food_id,
+        serving_id,
+        amount: check_positive("Amount", form.amount)?,
+        created_at: Utc::now(),
+    };
+
+    state
+        .db
+        .run(move |db| {
+            db.create_template_line(input)?;
+            Ok(Redirect::to(&TemplateViewHandler::url(template_id)))
+        })
+        .await
+}
@@ -0,0 +1,92 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::routing::get;
+use maud::html;
+
+use crate::db::MealTemplate;
+use crate::error::Fallible;
+use crate::routes::template_new::TemplateNewHandler;
+use crate::routes::template_view::TemplateViewHandler;
+use crate::ui::*;
+use crate::www::ServerState;
+
+pub struct TemplateListHandler {}
+
+impl TemplateListHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route(Self::url(), get(handler))
+    }
+
+    pub fn url() -> &'static str {
+        "/templates"
+    }
+}
+
+async fn handler(State(state): State<ServerState>) -> Fallible<(StatusCode, Html<String>)> {
+    state
+        .db
+        .run(move |db| {
+            let templates: Vec<MealTemplate> = db.list_meal_templates()?;
+
+            let content = html! {
+                p {
+                    "Templates are lists of foods that are often eaten together, which can be logged in one go from the log. Create them here, or from the entries of a day."
+                }
+                @if templates.is_empty() {
+                    p {
+                        "No templates."
+                    }
+                } @else {
+                    table {
+                        thead {
+                            tr {
+                                th { "Template" }
+                            }
+                        }
+                        tbody {
+                            @for template in &templates {
+                                tr {
+                                    td {
+                                        a href=(TemplateViewHandler::url(template.template_id)) {
+                                            (template.name)
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                h2 {
+                    "Add Template"
+                }
+                form method="post" action=(TemplateNewHandler::url()) {
+                    .form-group {
+                        (label("name", "Name"))
+                        (text_input("name", "name", "e.g., Weekday Breakfast"))
+                    }
+                    input .button type="submit" value="Add Template";
+                }
+            };
+
+            let html_page = page("Meal Templates", content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
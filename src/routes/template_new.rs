@@ -0,0 +1,65 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Form;
+use axum::Router;
+use axum::extract::State;
+use axum::response::Redirect;
+use axum::routing::post;
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::db::CreateMealTemplateInput;
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::routes::template_view::TemplateViewHandler;
+use crate::www::ServerState;
+
+pub struct TemplateNewHandler {}
+
+impl TemplateNewHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route(Self::url(), post(handler))
+    }
+
+    pub fn url() -> &'static str {
+        "/templates/new"
+    }
+}
+
+#[derive(Deserialize)]
+struct CreateTemplateForm {
+    name: String,
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Form(form): Form<CreateTemplateForm>,
+) -> Fallible<Redirect> {
+    let name: String = form.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::bad_request("Name is required."));
+    }
+    let input = CreateMealTemplateInput {
+        name,
+        created_at: Utc::now(),
+    };
+    state
+        .db
+        .run(move |db| {
+            let template_id = db.create_meal_template(input)?;
+            Ok(Redirect::to(&TemplateViewHandler::url(template_id)))
+        })
+        .await
+}
@@ -0,0 +1,122 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::routing::get;
+use maud::html;
+
+use crate::db::MealTemplate;
+use crate::db::TemplateId;
+use crate::db::TemplateLine;
+use crate::error::Fallible;
+use crate::routes::food_view::FoodViewHandler;
+use crate::routes::template_delete::TemplateDeleteHandler;
+use crate::routes::template_line_delete::TemplateLineDeleteHandler;
+use crate::routes::template_line_new::TemplateLineNewHandler;
+use crate::routes::template_list::TemplateListHandler;
+use crate::ui::*;
+use crate::www::ServerState;
+
+pub struct TemplateViewHandler {}
+
+impl TemplateViewHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route("/templates/{template_id}", get(handler))
+    }
+
+    pub fn url(template_id: TemplateId) -> String {
+        format!("/templates/{template_id}")
+    }
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Path(template_id): Path<TemplateId>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    state
+        .db
+        .run(move |db| {
+            let template: MealTemplate = db.get_meal_template(template_id)?;
+            let lines: Vec<TemplateLine> = db.list_template_lines(template_id)?;
+
+            let content = html! {
+                .button-bar {
+                    a .button href=(TemplateListHandler::url()) {
+                        "← Templates"
+                    }
+                    .spacer {}
+                    a .button href=(TemplateLineNewHandler::url(template_id)) {
+                        "Add Food"
+                    }
+                    form method="post" action=(TemplateDeleteHandler::url(template_id)) {
+                        input .button type="submit" value="Delete Template";
+                    }
+                }
+                @if lines.is_empty() {
+                    p {
+                        "This template has no foods yet."
+                    }
+                } @else {
+                    table {
+                        thead {
+                            tr {
+                                th { "Food" }
+                                th { "Brand" }
+                                th { "Amount" }
+                                th { "Delete" }
+                            }
+                        }
+                        tbody {
+                            @for line in &lines {
+                                @let unit_name: &str = match &line.serving_name {
+                                    Some(serving_name) => serving_name,
+                                    None => line.serving_unit.as_str(),
+                                };
+                                tr {
+                                    td {
+                                        a href=(FoodViewHandler::url(line.food_id)) {
+                                            (line.name)
+                                        }
+                                    }
+                                    td {
+                                        @if line.brand.is_empty() {
+                                            "—"
+                                        } @else {
+                                            (line.brand)
+                                        }
+                                    }
+                                    td {
+                                        (format!("{} {}", line.amount, unit_name))
+                                    }
+                                    td .center {
+                                        form method="post" action=(TemplateLineDeleteHandler::url(template_id, line.line_id)) {
+                                            input .button type="submit" value="Delete";
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            };
+
+            let html_page = page(&format!("Template: {}", template.name), content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
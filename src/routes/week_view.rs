@@ -0,0 +1,209 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Html;
+use axum::routing::get;
+use maud::Markup;
+use maud::html;
+
+use crate::db::DailyTotals;
+use crate::db::Target;
+use crate::error::Fallible;
+use crate::routes::calendar_view::CalendarViewHandler;
+use crate::routes::log_view::LogViewHandler;
+use crate::types::Date;
+use crate::types::EnergyUnit;
+use crate::types::Nutrient;
+use crate::types::Nutrition;
+use crate::ui::*;
+use crate::www::ServerState;
+
+/// Shows the daily totals of the week a date falls in.
+pub struct WeekViewHandler {}
+
+impl WeekViewHandler {
+    pub fn route(router: Router<ServerState>) -> Router<ServerState> {
+        router.route("/week/{date}", get(handler))
+    }
+
+    pub fn url(date: Date) -> String {
+        format!("/week/{}", date.week_start())
+    }
+}
+
+/// A day of the week, with its totals if anything was logged.
+struct WeekDay {
+    date: Date,
+    totals: Option<Nutrition>,
+    targets: Vec<Target>,
+}
+
+async fn handler(
+    State(state): State<ServerState>,
+    Path(date): Path<String>,
+) -> Fallible<(StatusCode, Html<String>)> {
+    let date: Date = Date::try_from(date)?;
+    let start: Date = date.week_start();
+    let end: Date = start.add_days(6);
+    let energy_unit: EnergyUnit = state.energy_unit;
+    state
+        .db
+        .run(move |db| {
+            let totals: Vec<DailyTotals> = db.list_daily_totals(start, end)?;
+            let mut targets: HashMap<Date, Vec<Target>> = db.list_day_targets(start, end)?;
+            let mut days: Vec<WeekDay> = Vec::new();
+            for offset in 0..7 {
+                let date = start.add_days(offset);
+                days.push(WeekDay {
+                    date,
+                    totals: totals
+                        .iter()
+                        .find(|day| day.date == date)
+                        .map(|day| day.nutrition),
+                    targets: targets.remove(&date).unwrap_or_default(),
+                });
+            }
+            let content = html! {
+                .button-bar {
+                    a .button href=(WeekViewHandler::url(start.add_days(-7))) {
+                        "← Previous"
+                    }
+                    a .button href=(WeekViewHandler::url(Date::today())) {
+                        "This Week"
+                    }
+                    a .button href=(WeekViewHandler::url(start.add_days(7))) {
+                        "Next →"
+                    }
+                    a .button href=(CalendarViewHandler::url(start)) {
+                        "Month"
+                    }
+                }
+                (render_week(&days, energy_unit))
+            };
+            let title = format!("Week of {}", start.humanize());
+            let html_page = page(&title, content);
+            Ok((StatusCode::OK, Html(html_page.into_string())))
+        })
+        .await
+}
+
+fn render_week(days: &[WeekDay], energy_unit: EnergyUnit) -> Markup {
+    let logged: Vec<&WeekDay> = days.iter().filter(|day| day.totals.is_some()).collect();
+    let sum: Nutrition = logged
+        .iter()
+        .filter_map(|day| day.totals)
+        .fold(Nutrition::zero(), |acc, t| acc + t);
+    html! {
+        table .week {
+            thead {
+                tr {
+                    th { "Day" }
+                    @for nutrient in Nutrient::ALL {
+                        th .numeric { (nutrient.label()) " (" (nutrient.display_unit(energy_unit)) ")" }
+                    }
+                }
+            }
+            tbody {
+                @for day in days {
+                    @match day.totals {
+                        Some(totals) => {
+                            tr {
+                                th {
+                                    a href=(LogViewHandler::url(day.date)) { (day.date.humanize_short()) }
+                                }
+                                @for nutrient in Nutrient::ALL {
+                                    (total_cell(nutrient, totals.get(nutrient), find_target(&day.targets, nutrient), energy_unit))
+                                }
+                            }
+                        }
+                        None => {
+                            tr .unlogged {
+                                th {
+                                    a href=(LogViewHandler::url(day.date)) { (day.date.humanize_short()) }
+                                }
+                                @for _ in Nutrient::ALL {
+                                    td .numeric { "—" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            tfoot {
+                tr {
+                    th { "Total" }
+                    @for nutrient in Nutrient::ALL {
+                        (total_cell(nutrient, sum.get(nutrient), None, energy_unit))
+                    }
+                }
+                tr {
+                    th { "Average" }
+                    @if logged.is_empty() {
+                        @for _ in Nutrient::ALL {
+                            td .numeric { "—" }
+                        }
+                    } @else {
+                        @let average = sum.scale(1.0 / logged.len() as f64);
+                        @for nutrient in Nutrient::ALL {
+                            (total_cell(nutrient, average.get(nutrient), common_target(&logged, nutrient), energy_unit))
+                        }
+                    }
+                }
+            }
+        }
+        p {
+            "Averages are over the " (logged.len()) " days with food logged."
+        }
+    }
+}
+
+/// A cell showing a total, highlighted against the target if there is one.
+fn total_cell(
+    nutrient: Nutrient,
+    total: f64,
+    target: Option<&Target>,
+    energy_unit: EnergyUnit,
+) -> Markup {
+    let value = humanize_float(nutrient.display_value(total, energy_unit));
+    html! {
+        @match target {
+            Some(target) => {
+                td .numeric .(target.status(total).as_str()) title=(target.describe(energy_unit)) { (value) }
+            }
+            None => {
+                td .numeric { (value) }
+            }
+        }
+    }
+}
+
+fn find_target(targets: &[Target], nutrient: Nutrient) -> Option<&Target> {
+    targets.iter().find(|target| target.nutrient == nutrient)
+}
+
+/// The target for a nutrient, if every logged day shares the same one. The
+/// average can only be judged against a target that applied all week.
+fn common_target<'a>(days: &[&'a WeekDay], nutrient: Nutrient) -> Option<&'a Target> {
+    let mut targets = days.iter().map(|day| find_target(&day.targets, nutrient));
+    let first = targets.next()??;
+    targets
+        .all(|target| target.is_some_and(|t| t.min == first.min && t.max == first.max))
+        .then_some(first)
+}
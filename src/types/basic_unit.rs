@@ -37,7 +37,7 @@ impl TryFrom<&str> for BasicUnit {
         match value {
             "g" => Ok(Self::Grams),
             "ml" => Ok(Self::Milliliters),
-            _ => Err(AppError::new("Invalid value for serving unit.")),
+            _ => Err(AppError::bad_request("Invalid value for serving unit.")),
         }
     }
 }
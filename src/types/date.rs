@@ -15,8 +15,11 @@
 use std::fmt::Display;
 use std::fmt::Formatter;
 
+use chrono::Datelike;
 use chrono::Local;
+use chrono::Months;
 use chrono::NaiveDate;
+use chrono::TimeDelta;
 use rusqlite::ToSql;
 use rusqlite::types::FromSql;
 use rusqlite::types::FromSqlError;
@@ -26,7 +29,7 @@ use rusqlite::types::ValueRef;
 
 use crate::error::AppError;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Date(NaiveDate);
 
 impl Date {
@@ -34,6 +37,25 @@ impl Date {
         Self(Local::now().naive_local().date())
     }
 
+    /// The date with the given year, month and day, if it exists.
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        NaiveDate::from_ymd_opt(year, month, day).map(Self)
+    }
+
+    pub fn year(self) -> i32 {
+        self.0.year()
+    }
+
+    /// Month of the year, from 1 to 12.
+    pub fn month(self) -> u32 {
+        self.0.month()
+    }
+
+    /// Day of the month, from 1.
+    pub fn day(self) -> u32 {
+        self.0.day()
+    }
+
     pub fn prev_day(self) -> Self {
         Self(self.0.pred_opt().unwrap_or(self.0))
     }
@@ -42,9 +64,57 @@ impl Date {
         Self(self.0.succ_opt().unwrap_or(self.0))
     }
 
+    /// The date `days` days after this one, or before it if negative.
+    pub fn add_days(self, days: i64) -> Self {
+        Self(self.0 + TimeDelta::days(days))
+    }
+
+    /// The number of days from this date to `other`, negative if `other` is
+    /// earlier.
+    pub fn days_until(self, other: Date) -> i64 {
+        (other.0 - self.0).num_days()
+    }
+
+    /// The Monday of the week this date falls in.
+    pub fn week_start(self) -> Self {
+        self.add_days(-i64::from(self.weekday()))
+    }
+
+    /// The first day of the month this date falls in.
+    pub fn month_start(self) -> Self {
+        Self(self.0.with_day(1).unwrap_or(self.0))
+    }
+
+    /// The first day of the following month.
+    pub fn next_month(self) -> Self {
+        let start = self.month_start().0;
+        Self(start.checked_add_months(Months::new(1)).unwrap_or(start))
+    }
+
+    /// The first day of the preceding month.
+    pub fn prev_month(self) -> Self {
+        let start = self.month_start().0;
+        Self(start.checked_sub_months(Months::new(1)).unwrap_or(start))
+    }
+
+    /// Day of the week, from 0 (Monday) to 6 (Sunday).
+    pub fn weekday(self) -> u32 {
+        self.0.weekday().num_days_from_monday()
+    }
+
     pub fn humanize(&self) -> String {
         self.0.format("%A, %d %B %Y").to_string()
     }
+
+    /// The month and year, e.g. "October 2025".
+    pub fn humanize_month(&self) -> String {
+        self.0.format("%B %Y").to_string()
+    }
+
+    /// Like `humanize`, but abbreviated and without the year.
+    pub fn humanize_short(&self) -> String {
+        self.0.format("%a %d %b").to_string()
+    }
 }
 
 impl Display for Date {
@@ -58,7 +128,7 @@ impl TryFrom<String> for Date {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let date = NaiveDate::parse_from_str(&value, "%Y-%m-%d")
-            .map_err(|_| AppError::new(format!("invalid date: {}", value)))?;
+            .map_err(|_| AppError::bad_request(format!("invalid date: {}", value)))?;
         Ok(Date(date))
     }
 }
@@ -0,0 +1,70 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::Deserialize;
+
+use crate::error::AppError;
+use crate::error::Fallible;
+
+const KJ_PER_KCAL: f64 = 4.184;
+
+/// The unit energy is entered or displayed in. Energy is always stored in
+/// kilocalories.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum EnergyUnit {
+    #[default]
+    #[serde(rename = "kcal")]
+    Kilocalories,
+    #[serde(rename = "kJ")]
+    Kilojoules,
+}
+
+impl EnergyUnit {
+    pub const ALL: [EnergyUnit; 2] = [Self::Kilocalories, Self::Kilojoules];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Kilocalories => "kcal",
+            Self::Kilojoules => "kJ",
+        }
+    }
+
+    /// Convert an amount of energy in kilocalories to this unit.
+    pub fn convert_kcal(self, kcal: f64) -> f64 {
+        match self {
+            Self::Kilocalories => kcal,
+            Self::Kilojoules => kcal * KJ_PER_KCAL,
+        }
+    }
+
+    /// Convert an amount of energy in this unit to kilocalories.
+    pub fn to_kcal(self, value: f64) -> f64 {
+        match self {
+            Self::Kilocalories => value,
+            Self::Kilojoules => value / KJ_PER_KCAL,
+        }
+    }
+}
+
+impl TryFrom<&str> for EnergyUnit {
+    type Error = AppError;
+
+    fn try_from(value: &str) -> Fallible<Self> {
+        match value {
+            "kcal" => Ok(Self::Kilocalories),
+            "kJ" => Ok(Self::Kilojoules),
+            _ => Err(AppError::bad_request("Invalid value for energy unit.")),
+        }
+    }
+}
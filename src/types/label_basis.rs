@@ -0,0 +1,66 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::types::Nutrition;
+
+/// What the nutrition values on a label are given for. Foods always store
+/// values per 100 of their base unit.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LabelBasis {
+    /// Per 100g or 100ml.
+    Per100,
+    /// Per serving, of a size given separately.
+    PerServing,
+}
+
+impl LabelBasis {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Per100 => "per_100",
+            Self::PerServing => "per_serving",
+        }
+    }
+
+    /// Convert values read off a label to values per 100 of the base unit.
+    /// `serving_size` is the size of the label's serving, in the base unit.
+    pub fn to_per_100(
+        self,
+        nutrition: Nutrition,
+        serving_size: Option<f64>,
+    ) -> Fallible<Nutrition> {
+        match self {
+            Self::Per100 => Ok(nutrition),
+            Self::PerServing => match serving_size {
+                Some(size) if size > 0.0 => Ok(nutrition.scale(100.0 / size)),
+                _ => Err(AppError::bad_request(
+                    "A positive serving size is needed to enter values per serving.",
+                )),
+            },
+        }
+    }
+}
+
+impl TryFrom<&str> for LabelBasis {
+    type Error = AppError;
+
+    fn try_from(value: &str) -> Fallible<Self> {
+        match value {
+            "per_100" => Ok(Self::Per100),
+            "per_serving" => Ok(Self::PerServing),
+            _ => Err(AppError::bad_request("Invalid value for label basis.")),
+        }
+    }
+}
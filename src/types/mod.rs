@@ -14,8 +14,16 @@
 
 mod basic_unit;
 mod date;
+mod energy_unit;
+mod label_basis;
+mod nutrient;
 mod nutrition;
+mod time;
 
 pub use basic_unit::*;
 pub use date::*;
+pub use energy_unit::*;
+pub use label_basis::*;
+pub use nutrient::*;
 pub use nutrition::*;
+pub use time::*;
@@ -0,0 +1,132 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::AppError;
+use crate::error::Fallible;
+use crate::types::EnergyUnit;
+use crate::types::Nutrition;
+
+/// One of the nutrients tracked in `Nutrition`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Nutrient {
+    Energy,
+    Protein,
+    Fat,
+    FatSaturated,
+    Carbs,
+    CarbsSugars,
+    Fibre,
+    Sodium,
+}
+
+impl Nutrient {
+    /// All nutrients, in the order they are listed on a label.
+    pub const ALL: [Nutrient; 8] = [
+        Self::Energy,
+        Self::Protein,
+        Self::Fat,
+        Self::FatSaturated,
+        Self::Carbs,
+        Self::CarbsSugars,
+        Self::Fibre,
+        Self::Sodium,
+    ];
+
+    /// The name of the nutrient's column in the database.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Energy => "energy",
+            Self::Protein => "protein",
+            Self::Fat => "fat",
+            Self::FatSaturated => "fat_saturated",
+            Self::Carbs => "carbs",
+            Self::CarbsSugars => "carbs_sugars",
+            Self::Fibre => "fibre",
+            Self::Sodium => "sodium",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Energy => "Energy",
+            Self::Protein => "Protein",
+            Self::Fat => "Fat",
+            Self::FatSaturated => "Fat — Saturated",
+            Self::Carbs => "Carbs",
+            Self::CarbsSugars => "Carbs — Sugars",
+            Self::Fibre => "Fibre",
+            Self::Sodium => "Sodium",
+        }
+    }
+
+    pub fn unit(self) -> &'static str {
+        match self {
+            Self::Energy => "kcal",
+            Self::Sodium => "mg",
+            _ => "g",
+        }
+    }
+
+    /// The unit the nutrient is displayed in, given the preferred energy unit.
+    pub fn display_unit(self, energy_unit: EnergyUnit) -> &'static str {
+        match self {
+            Self::Energy => energy_unit.as_str(),
+            _ => self.unit(),
+        }
+    }
+
+    /// Convert an amount of the nutrient, in the units of `unit`, to the units
+    /// of `display_unit`.
+    pub fn display_value(self, value: f64, energy_unit: EnergyUnit) -> f64 {
+        match self {
+            Self::Energy => energy_unit.convert_kcal(value),
+            _ => value,
+        }
+    }
+
+    /// Convert an amount of the nutrient, in the units of `display_unit`,
+    /// back to the units of `unit`.
+    pub fn stored_value(self, value: f64, energy_unit: EnergyUnit) -> f64 {
+        match self {
+            Self::Energy => energy_unit.to_kcal(value),
+            _ => value,
+        }
+    }
+}
+
+impl TryFrom<&str> for Nutrient {
+    type Error = AppError;
+
+    fn try_from(value: &str) -> Fallible<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|n| n.as_str() == value)
+            .ok_or_else(|| AppError::bad_request(format!("Invalid nutrient: {value}")))
+    }
+}
+
+impl Nutrition {
+    pub fn get(&self, nutrient: Nutrient) -> f64 {
+        match nutrient {
+            Nutrient::Energy => self.energy,
+            Nutrient::Protein => self.protein,
+            Nutrient::Fat => self.fat,
+            Nutrient::FatSaturated => self.fat_saturated,
+            Nutrient::Carbs => self.carbs,
+            Nutrient::CarbsSugars => self.carbs_sugars,
+            Nutrient::Fibre => self.fibre,
+            Nutrient::Sodium => self.sodium,
+        }
+    }
+}
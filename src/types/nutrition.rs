@@ -15,6 +15,20 @@
 use std::ops::Add;
 
 use crate::db::*;
+use crate::types::BasicUnit;
+use crate::validation::FieldErrors;
+
+/// Energy, in kcal per gram, of protein, fat, carbohydrate and fibre.
+const ATWATER_PROTEIN: f64 = 4.0;
+const ATWATER_FAT: f64 = 9.0;
+const ATWATER_CARBS: f64 = 4.0;
+const ATWATER_FIBRE: f64 = 2.0;
+
+/// How far the stated energy can be from the estimate before it's flagged,
+/// both as a fraction of the estimate and in kcal. Labels round their values,
+/// so small foods need some absolute slack.
+const ENERGY_TOLERANCE_RATIO: f64 = 0.15;
+const ENERGY_TOLERANCE_KCAL: f64 = 15.0;
 
 /// The nutritional content of some amount of food.
 #[derive(Clone, Copy)]
@@ -30,6 +44,19 @@ pub struct Nutrition {
 }
 
 impl Nutrition {
+    pub fn zero() -> Self {
+        Self {
+            energy: 0.0,
+            protein: 0.0,
+            fat: 0.0,
+            fat_saturated: 0.0,
+            carbs: 0.0,
+            carbs_sugars: 0.0,
+            fibre: 0.0,
+            sodium: 0.0,
+        }
+    }
+
     pub fn scale(self, factor: f64) -> Self {
         Self {
             energy: self.energy * factor,
@@ -44,6 +71,48 @@ impl Nutrition {
     }
 }
 
+impl Nutrition {
+    /// Estimate energy from the macronutrients, using Atwater factors.
+    pub fn atwater_energy(&self) -> Energy {
+        self.protein * ATWATER_PROTEIN
+            + self.fat * ATWATER_FAT
+            + self.carbs * ATWATER_CARBS
+            + self.fibre * ATWATER_FIBRE
+    }
+
+    /// Check that nutrition values per 100 of `unit` are physically possible,
+    /// recording errors against the fields of the food form.
+    pub fn validate(&self, unit: BasicUnit, errors: &mut FieldErrors) {
+        if self.carbs_sugars > self.carbs {
+            errors.add("carbs_sugars", "Sugars can't exceed total carbohydrate.");
+        }
+        if self.fat_saturated > self.fat {
+            errors.add("fat_saturated", "Saturated fat can't exceed total fat.");
+        }
+        // Liquids can be denser than water, so 100ml can hold more than 100g.
+        if let BasicUnit::Grams = unit {
+            let macros = self.protein + self.fat + self.carbs + self.fibre;
+            if macros > 100.0 {
+                errors.add(
+                    "nutrition",
+                    format!(
+                        "Protein, fat, carbohydrate and fibre add up to {macros:.1}g, more than 100g per 100g."
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Whether the stated energy is far from the Atwater estimate, which
+    /// usually means a typo. The tolerance has an absolute part, so this
+    /// expects values per 100 of the base unit.
+    pub fn energy_is_suspicious(&self) -> bool {
+        let estimate = self.atwater_energy();
+        let difference = (self.energy - estimate).abs();
+        difference > ENERGY_TOLERANCE_KCAL && difference > estimate * ENERGY_TOLERANCE_RATIO
+    }
+}
+
 impl Add<Nutrition> for Nutrition {
     type Output = Nutrition;
 
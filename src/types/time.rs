@@ -0,0 +1,78 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use chrono::Local;
+use chrono::NaiveTime;
+use chrono::Timelike;
+use rusqlite::ToSql;
+use rusqlite::types::FromSql;
+use rusqlite::types::FromSqlError;
+use rusqlite::types::FromSqlResult;
+use rusqlite::types::ToSqlOutput;
+use rusqlite::types::ValueRef;
+
+use crate::error::AppError;
+
+/// A time of day, with minute precision.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Time(NaiveTime);
+
+impl Time {
+    pub fn now() -> Self {
+        let now = Local::now();
+        Self(NaiveTime::from_hms_opt(now.hour(), now.minute(), 0).unwrap_or_default())
+    }
+
+    pub fn from_hm(hour: u32, min: u32) -> Option<Self> {
+        NaiveTime::from_hms_opt(hour, min, 0).map(Self)
+    }
+
+    /// The number of minutes since midnight.
+    pub fn minutes(self) -> u32 {
+        self.0.hour() * 60 + self.0.minute()
+    }
+}
+
+impl Display for Time {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.format("%H:%M"))
+    }
+}
+
+impl TryFrom<String> for Time {
+    type Error = AppError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let time = NaiveTime::parse_from_str(&value, "%H:%M")
+            .map_err(|_| AppError::bad_request(format!("invalid time: {}", value)))?;
+        Ok(Time(time))
+    }
+}
+
+impl ToSql for Time {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let str = self.to_string();
+        Ok(ToSqlOutput::from(str))
+    }
+}
+
+impl FromSql for Time {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let string: String = FromSql::column_result(value)?;
+        Time::try_from(string).map_err(|e| FromSqlError::Other(Box::new(e)))
+    }
+}
@@ -18,8 +18,17 @@ use maud::html;
 
 use crate::routes::assets::CssHandler;
 use crate::routes::assets::CssResetHandler;
+use crate::routes::calendar_view::CalendarViewHandler;
 use crate::routes::food_list::FoodListHandler;
+use crate::routes::meal_slot_list::MealSlotListHandler;
+use crate::routes::report_view::ReportViewHandler;
 use crate::routes::root::RootHandler;
+use crate::routes::target_profile_list::TargetProfileListHandler;
+use crate::routes::template_list::TemplateListHandler;
+use crate::types::Date;
+use crate::types::EnergyUnit;
+use crate::types::LabelBasis;
+use crate::validation::FieldErrors;
 
 /// Page template with sidebar navigation
 pub fn page(title: &str, body: Markup) -> Markup {
@@ -43,11 +52,36 @@ pub fn page(title: &str, body: Markup) -> Markup {
                                         "Today"
                                     }
                                 }
+                                li {
+                                    a href=(CalendarViewHandler::url(Date::today())) {
+                                        "Calendar"
+                                    }
+                                }
                                 li {
                                     a href=(FoodListHandler::url()) {
                                         "Library"
                                     }
                                 }
+                                li {
+                                    a href=(MealSlotListHandler::url()) {
+                                        "Meals"
+                                    }
+                                }
+                                li {
+                                    a href=(TemplateListHandler::url()) {
+                                        "Templates"
+                                    }
+                                }
+                                li {
+                                    a href=(ReportViewHandler::url()) {
+                                        "Reports"
+                                    }
+                                }
+                                li {
+                                    a href=(TargetProfileListHandler::url()) {
+                                        "Targets"
+                                    }
+                                }
                             }
                         }
                     }
@@ -152,6 +186,27 @@ pub fn number_input(id: &str, name: &str, step: &str, placeholder: &str) -> Mark
     }
 }
 
+/// Number input with value
+pub fn number_input_value(id: &str, name: &str, step: &str, value: &str) -> Markup {
+    html! {
+        input type="number" id=(id) name=(name) step=(step) value=(value);
+    }
+}
+
+/// Date input
+pub fn date_input(id: &str, name: &str) -> Markup {
+    html! {
+        input type="date" id=(id) name=(name);
+    }
+}
+
+/// Date input with value
+pub fn date_input_value(id: &str, name: &str, value: &str) -> Markup {
+    html! {
+        input type="date" id=(id) name=(name) value=(value);
+    }
+}
+
 /// Select dropdown
 pub fn select(id: &str, name: &str, options: Vec<(String, String)>) -> Markup {
     html! {
@@ -183,12 +238,13 @@ pub fn select_with_selected(
     }
 }
 
-/// Nutrition table row (for form input)
-pub fn nutrition_row(
+/// Nutrition table row with value (for editing)
+pub fn nutrition_row_with_value(
     label_text: &str,
     input_id: &str,
     input_name: &str,
     unit: &str,
+    value: &str,
     indent: u8,
 ) -> Markup {
     let class = match indent {
@@ -207,40 +263,127 @@ pub fn nutrition_row(
                 (label_text)
             }
             div."nutrition-input" {
-                input type="number" id=(input_id) name=(input_name) step="0.1" placeholder="0.0";
+                input type="number" id=(input_id) name=(input_name) value=(value) step="any" placeholder="0.0";
                 span."nutrition-unit" { (unit) }
             }
         }
     }
 }
 
-/// Nutrition table row with value (for editing)
-pub fn nutrition_row_with_value(
+/// Nutrition table row for energy, with a choice of unit
+pub fn energy_row(label_text: &str, value: &str, unit: EnergyUnit) -> Markup {
+    html! {
+        div."nutrition-row" {
+            div."nutrition-label" {
+                (label_text)
+            }
+            div."nutrition-input" {
+                input type="number" id="energy" name="energy" value=(value) step="any" placeholder="0.0";
+                select."nutrition-unit" name="energy_unit" {
+                    @for option in EnergyUnit::ALL {
+                        @if option == unit {
+                            option value=(option.as_str()) selected { (option.as_str()) }
+                        } @else {
+                            option value=(option.as_str()) { (option.as_str()) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Error message for a form field, if it has one
+pub fn field_error(errors: &FieldErrors, field: &str) -> Markup {
+    html! {
+        @if let Some(message) = errors.get(field) {
+            div."field-error" {
+                (message)
+            }
+        }
+    }
+}
+
+/// Nutrition table row with value and error message
+pub fn nutrition_row_checked(
     label_text: &str,
-    input_id: &str,
-    input_name: &str,
+    name: &str,
     unit: &str,
     value: &str,
     indent: u8,
+    errors: &FieldErrors,
 ) -> Markup {
-    let class = match indent {
-        1 => "nutrition-row indent-1",
-        2 => "nutrition-row indent-2",
-        _ => "nutrition-row",
-    };
-    let label_class = if indent > 0 {
-        "nutrition-label sub"
-    } else {
-        "nutrition-label"
-    };
     html! {
-        div.(class) {
-            div.(label_class) {
-                (label_text)
+        (nutrition_row_with_value(label_text, name, name, unit, value, indent))
+        (field_error(errors, name))
+    }
+}
+
+/// Form section for entering nutrition per serving, as printed on labels
+pub fn label_basis_section(
+    basis: &str,
+    serving_size: &str,
+    serving_name: &str,
+    errors: &FieldErrors,
+) -> Markup {
+    form_section(
+        "Label",
+        html! {
+            (form_row(html! {
+                (form_group_half(html! {
+                    (label_required("basis", "Values Are Per"))
+                    (select_with_selected("basis", "basis", vec![
+                        (LabelBasis::Per100.as_str().to_string(), "100g or 100ml".to_string()),
+                        (LabelBasis::PerServing.as_str().to_string(), "Serving".to_string()),
+                    ], basis))
+                }))
+                (form_group_half(html! {
+                    (label_with_hint("serving_size", "Serving Size", "(in the base unit, required for values per serving)"))
+                    input type="number" id="serving_size" name="serving_size" step="0.1" placeholder="e.g., 30" value=(serving_size);
+                    (field_error(errors, "serving_size"))
+                }))
+            }))
+            (form_row(html! {
+                (form_group(html! {
+                    (label_with_hint("serving_name", "Save Serving As", "(optional, adds a serving of this size to the food)"))
+                    (text_input_value("serving_name", "serving_name", serving_name, "e.g., biscuit"))
+                }))
+            }))
+        },
+    )
+}
+
+/// Confirmation form that re-submits the given fields
+pub fn confirm_form(
+    action: &str,
+    fields: &[(&str, String)],
+    submit: &str,
+    cancel_url: &str,
+) -> Markup {
+    html! {
+        form .button-bar method="post" action=(action) {
+            @for (name, value) in fields {
+                input type="hidden" name=(name) value=(value);
             }
-            div."nutrition-input" {
-                input type="number" id=(input_id) name=(input_name) value=(value) step="0.1" placeholder="0.0";
-                span."nutrition-unit" { (unit) }
+            input type="hidden" name="confirmed" value="true";
+            input .button type="submit" value=(submit);
+            a .button href=(cancel_url) {
+                "Cancel"
+            }
+        }
+    }
+}
+
+/// Food search form that submits the query as `q` to `action`
+pub fn search_form(action: &str, query: &str) -> Markup {
+    html! {
+        form .search-form method="get" action=(action) {
+            input type="search" name="q" value=(query) placeholder="Search by name or brand" aria-label="Search";
+            input .button type="submit" value="Search";
+            @if !query.is_empty() {
+                a .button href=(action) {
+                    "Clear"
+                }
             }
         }
     }
@@ -254,3 +397,8 @@ pub fn nutrition_table(rows: Markup) -> Markup {
         }
     }
 }
+
+/// Format a quantity for display, to one decimal place.
+pub fn humanize_float(f: f64) -> String {
+    format!("{:.1}", f)
+}
@@ -0,0 +1,130 @@
+// Copyright 2025 Fernando Borretti
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::error::AppError;
+use crate::error::Fallible;
+
+/// Errors found while validating a submitted form, keyed by field name, so
+/// the form can be shown again with each message next to its input.
+#[derive(Default)]
+pub struct FieldErrors {
+    errors: Vec<(String, String)>,
+}
+
+impl FieldErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an error for a field. Only the first error for each field is
+    /// kept.
+    pub fn add(&mut self, field: &str, message: impl ToString) {
+        if self.get(field).is_none() {
+            self.errors.push((field.to_string(), message.to_string()));
+        }
+    }
+
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.errors
+            .iter()
+            .find(|(f, _)| f == field)
+            .map(|(_, message)| message.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Record an error if a required text field is blank.
+    pub fn required(&mut self, field: &str, value: &str) {
+        if value.trim().is_empty() {
+            self.add(field, "Required.");
+        }
+    }
+
+    /// Parse a required number that can't be negative. If it's invalid, the
+    /// error is recorded and zero returned.
+    pub fn non_negative(&mut self, field: &str, value: &str) -> f64 {
+        match parse_number(value) {
+            Ok(n) if n < 0.0 => {
+                self.add(field, "Can't be negative.");
+                0.0
+            }
+            Ok(n) => n,
+            Err(message) => {
+                self.add(field, message);
+                0.0
+            }
+        }
+    }
+
+    /// Like `non_negative`, but a blank value counts as zero.
+    pub fn optional_non_negative(&mut self, field: &str, value: &str) -> f64 {
+        if value.trim().is_empty() {
+            0.0
+        } else {
+            self.non_negative(field, value)
+        }
+    }
+
+    /// Parse a required number that must be greater than zero. If it's
+    /// invalid, the error is recorded and zero returned.
+    pub fn positive(&mut self, field: &str, value: &str) -> f64 {
+        match parse_number(value) {
+            Ok(n) if n <= 0.0 => {
+                self.add(field, "Must be greater than zero.");
+                0.0
+            }
+            Ok(n) => n,
+            Err(message) => {
+                self.add(field, message);
+                0.0
+            }
+        }
+    }
+
+    /// Like `positive`, but a blank value is `None` rather than an error.
+    pub fn optional_positive(&mut self, field: &str, value: &str) -> Option<f64> {
+        if value.trim().is_empty() {
+            None
+        } else {
+            Some(self.positive(field, value))
+        }
+    }
+}
+
+/// Check that an amount is a finite number greater than zero, for forms that
+/// aren't shown again on error.
+pub fn check_positive(label: &str, value: f64) -> Fallible<f64> {
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(AppError::bad_request(format!(
+            "{label} must be a number greater than zero."
+        )))
+    }
+}
+
+fn parse_number(value: &str) -> Result<f64, &'static str> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("Required.");
+    }
+    match value.parse::<f64>() {
+        // `parse` accepts "NaN" and "inf".
+        Ok(n) if n.is_finite() => Ok(n),
+        Ok(_) => Err("Must be a finite number."),
+        Err(_) => Err("Must be a number."),
+    }
+}
@@ -12,58 +12,143 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
-use std::sync::Mutex;
-
+use axum::Json;
 use axum::Router;
+use axum::body::to_bytes;
+use axum::extract::Request;
+use axum::http::header::ACCEPT;
+use axum::http::header::CONTENT_TYPE;
+use axum::middleware;
+use axum::middleware::Next;
+use axum::response::Html;
+use axum::response::IntoResponse;
+use axum::response::Response;
 use axum::routing::IntoMakeService;
+use maud::html;
+use serde::Serialize;
 use tokio::net::TcpListener;
 
 use crate::config::Config;
 use crate::db::Db;
+use crate::db::SharedDb;
 use crate::error::Fallible;
 use crate::routes::assets::CssHandler;
 use crate::routes::assets::CssResetHandler;
 use crate::routes::assets::FaviconHandler;
+use crate::routes::calendar_view::CalendarViewHandler;
+use crate::routes::food_archive::FoodArchiveHandler;
+use crate::routes::food_delete::FoodDeleteHandler;
 use crate::routes::food_edit::FoodEditHandler;
 use crate::routes::food_list::FoodListHandler;
 use crate::routes::food_new::FoodNewHandler;
 use crate::routes::food_view::FoodViewHandler;
+use crate::routes::ingredient_delete::IngredientDeleteHandler;
+use crate::routes::ingredient_new::IngredientNewHandler;
+use crate::routes::log_copy::LogCopyHandler;
 use crate::routes::log_delete::LogDeleteHandler;
+use crate::routes::log_edit::LogEditHandler;
+use crate::routes::log_move::LogMoveHandler;
 use crate::routes::log_new::LogNewHandler;
+use crate::routes::log_profile::LogProfileHandler;
+use crate::routes::log_quick_add::LogQuickAddHandler;
+use crate::routes::log_quick_convert::LogQuickConvertHandler;
+use crate::routes::log_save_template::LogSaveTemplateHandler;
+use crate::routes::log_template::LogTemplateHandler;
 use crate::routes::log_view::LogViewHandler;
+use crate::routes::meal_slot_delete::MealSlotDeleteHandler;
+use crate::routes::meal_slot_list::MealSlotListHandler;
+use crate::routes::meal_slot_new::MealSlotNewHandler;
+use crate::routes::recipe_edit::RecipeEditHandler;
+use crate::routes::recipe_new::RecipeNewHandler;
+use crate::routes::report_view::ReportViewHandler;
 use crate::routes::root::RootHandler;
 use crate::routes::serving_delete::ServingDeleteHandler;
 use crate::routes::serving_new::ServingNewHandler;
+use crate::routes::target_edit::TargetEditHandler;
+use crate::routes::target_profile_delete::TargetProfileDeleteHandler;
+use crate::routes::target_profile_list::TargetProfileListHandler;
+use crate::routes::target_profile_new::TargetProfileNewHandler;
+use crate::routes::target_weekdays::TargetWeekdaysHandler;
+use crate::routes::template_delete::TemplateDeleteHandler;
+use crate::routes::template_line_delete::TemplateLineDeleteHandler;
+use crate::routes::template_line_new::TemplateLineNewHandler;
+use crate::routes::template_list::TemplateListHandler;
+use crate::routes::template_new::TemplateNewHandler;
+use crate::routes::template_view::TemplateViewHandler;
+use crate::routes::week_view::WeekViewHandler;
+use crate::types::EnergyUnit;
+use crate::ui::page;
 
 #[derive(Clone)]
 pub struct ServerState {
-    pub db: Arc<Mutex<Db>>,
+    pub db: SharedDb,
+    /// The unit energy is displayed in.
+    pub energy_unit: EnergyUnit,
 }
 
 pub async fn start_server() -> Fallible<()> {
     let config: Config = Config::load()?;
     println!("Database: {}", config.db_path.display());
     let port: u16 = config.port;
-    let db: Db = Db::new(&config.db_path)?;
+    let mut db: Db = Db::open(&config.db_path)?;
+    for migration in db.migrate()? {
+        println!(
+            "Applied migration {:04}: {}",
+            migration.version, migration.name
+        );
+    }
     let state: ServerState = ServerState {
-        db: Arc::new(Mutex::new(db)),
+        db: SharedDb::new(db),
+        energy_unit: config.energy_unit,
     };
     let app: Router<ServerState> = Router::new();
 
     let app = CssHandler::route(app);
     let app = CssResetHandler::route(app);
     let app = FaviconHandler::route(app);
+    let app = CalendarViewHandler::route(app);
+    let app = FoodArchiveHandler::route(app);
+    let app = FoodDeleteHandler::route(app);
     let app = FoodEditHandler::route(app);
     let app = FoodListHandler::route(app);
     let app = FoodNewHandler::route(app);
     let app = FoodViewHandler::route(app);
+    let app = IngredientDeleteHandler::route(app);
+    let app = IngredientNewHandler::route(app);
+    let app = LogCopyHandler::route(app);
     let app = LogDeleteHandler::route(app);
+    let app = LogEditHandler::route(app);
+    let app = LogMoveHandler::route(app);
     let app = LogNewHandler::route(app);
+    let app = LogProfileHandler::route(app);
+    let app = LogQuickAddHandler::route(app);
+    let app = LogQuickConvertHandler::route(app);
+    let app = LogSaveTemplateHandler::route(app);
+    let app = LogTemplateHandler::route(app);
     let app = LogViewHandler::route(app);
+    let app = MealSlotDeleteHandler::route(app);
+    let app = MealSlotListHandler::route(app);
+    let app = MealSlotNewHandler::route(app);
+    let app = RecipeEditHandler::route(app);
+    let app = RecipeNewHandler::route(app);
+    let app = ReportViewHandler::route(app);
     let app = RootHandler::route(app);
     let app = ServingDeleteHandler::route(app);
     let app = ServingNewHandler::route(app);
+    let app = TargetEditHandler::route(app);
+    let app = TargetProfileDeleteHandler::route(app);
+    let app = TargetProfileListHandler::route(app);
+    let app = TargetProfileNewHandler::route(app);
+    let app = TargetWeekdaysHandler::route(app);
+    let app = TemplateDeleteHandler::route(app);
+    let app = TemplateLineDeleteHandler::route(app);
+    let app = TemplateLineNewHandler::route(app);
+    let app = TemplateListHandler::route(app);
+    let app = TemplateNewHandler::route(app);
+    let app = TemplateViewHandler::route(app);
+    let app = WeekViewHandler::route(app);
+
+    let app = app.layer(middleware::from_fn(render_errors));
 
     let app: IntoMakeService<Router> = app.with_state(state).into_make_service();
     let bind: String = format!("0.0.0.0:{port}");
@@ -72,3 +157,56 @@ pub async fn start_server() -> Fallible<()> {
     axum::serve(listener, app).await?;
     Ok(())
 }
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+/// Turn plain-text error responses, whether from `AppError` or from axum's
+/// own rejections, into a JSON object for API clients and an HTML page for
+/// everyone else.
+async fn render_errors(request: Request, next: Next) -> Response {
+    let wants_json = request
+        .headers()
+        .get(ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .is_some_and(|accept| accept.contains("application/json") && !accept.contains("text/html"));
+    let response = next.run(request).await;
+    let status = response.status();
+    let is_html = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("text/html"));
+    // Validation errors re-render their form, so they're already HTML.
+    if !(status.is_client_error() || status.is_server_error()) || is_html {
+        return response;
+    }
+    let reason = status.canonical_reason().unwrap_or("Error");
+    let body = to_bytes(response.into_body(), ERROR_BODY_LIMIT)
+        .await
+        .unwrap_or_default();
+    let message = match String::from_utf8_lossy(&body).trim() {
+        "" => reason.to_string(),
+        message => message.to_string(),
+    };
+    if wants_json {
+        (status, Json(ErrorBody { error: message })).into_response()
+    } else {
+        let content = html! {
+            p {
+                (message)
+            }
+            .button-bar {
+                a .button href=(RootHandler::url()) {
+                    "Home"
+                }
+            }
+        };
+        (status, Html(page(reason, content).into_string())).into_response()
+    }
+}
+
+/// Error bodies are short messages. Longer ones are replaced by the status.
+const ERROR_BODY_LIMIT: usize = 64 * 1024;